@@ -11,11 +11,13 @@
 //! - Array iteration
 //! - Comparison of construction methods
 //! - Performance with large strings
+//! - Packed single-allocation storage versus per-string allocation
+//! - Inline pointer table for small arrays
 
 use std::{convert::TryFrom, ffi::CString, hint::black_box};
 
-use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
-use cstring_array::CStringArray;
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use cstring_array::{CStringArray, PackedCStringArray, SmallCStringArray};
 
 fn bench_new_from_strings(c: &mut Criterion) {
     let mut group = c.benchmark_group("new_from_strings");
@@ -175,6 +177,137 @@ fn bench_large_strings(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_packed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("packed");
+
+    for size in [10, 100, 1000, 10000].iter() {
+        let strings: Vec<String> = (0..*size).map(|i| format!("string_{}", i)).collect();
+        let cstrings: Vec<CString> = strings
+            .iter()
+            .map(|s| CString::new(s.as_str()).unwrap())
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("new", size), size, |b, _| {
+            b.iter_batched(
+                || strings.clone(),
+                |strings| black_box(CStringArray::new(black_box(strings)).unwrap()),
+                BatchSize::SmallInput
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("from_cstrings", size), size, |b, _| {
+            b.iter_batched(
+                || cstrings.clone(),
+                |cstrings| black_box(CStringArray::from_cstrings(black_box(cstrings)).unwrap()),
+                BatchSize::SmallInput
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("packed_new", size), size, |b, _| {
+            b.iter(|| {
+                let array = PackedCStringArray::new(black_box(&strings)).unwrap();
+                black_box(array);
+            });
+        });
+
+        let array = CStringArray::new(strings.clone()).unwrap();
+        let packed = PackedCStringArray::new(&strings).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("clone", size), size, |b, _| {
+            b.iter(|| black_box(black_box(&array).clone()));
+        });
+
+        group.bench_with_input(BenchmarkId::new("packed_clone", size), size, |b, _| {
+            b.iter(|| black_box(black_box(&packed).clone()));
+        });
+
+        group.bench_with_input(BenchmarkId::new("iter", size), size, |b, _| {
+            b.iter(|| {
+                for item in black_box(&array).iter() {
+                    black_box(item);
+                }
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("packed_iter", size), size, |b, _| {
+            b.iter(|| {
+                for item in black_box(&packed).iter() {
+                    black_box(item);
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small");
+
+    for size in [1, 4, 8].iter() {
+        let cstrings: Vec<CString> = (0..*size)
+            .map(|i| CString::new(format!("arg_{}", i)).unwrap())
+            .collect();
+
+        group.bench_with_input(BenchmarkId::new("from_cstrings", size), size, |b, _| {
+            b.iter_batched(
+                || cstrings.clone(),
+                |cstrings| {
+                    let array = CStringArray::from_cstrings(black_box(cstrings)).unwrap();
+                    black_box(array.as_ptr());
+                },
+                BatchSize::SmallInput
+            );
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("small_from_cstrings", size),
+            size,
+            |b, _| {
+                b.iter_batched(
+                    || cstrings.clone(),
+                    |cstrings| {
+                        let array =
+                            SmallCStringArray::<8>::from_cstrings(black_box(cstrings)).unwrap();
+                        black_box(array.as_ptr());
+                    },
+                    BatchSize::SmallInput
+                );
+            }
+        );
+
+        group.bench_with_input(BenchmarkId::new("push", size), size, |b, _| {
+            b.iter_batched(
+                || cstrings.clone(),
+                |cstrings| {
+                    let mut array = CStringArray::empty();
+                    for s in black_box(cstrings) {
+                        array.push(s);
+                    }
+                    black_box(array.as_ptr());
+                },
+                BatchSize::SmallInput
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("small_push", size), size, |b, _| {
+            b.iter_batched(
+                || cstrings.clone(),
+                |cstrings| {
+                    let mut array = SmallCStringArray::<8>::empty();
+                    for s in black_box(cstrings) {
+                        array.push(s);
+                    }
+                    black_box(array.as_ptr());
+                },
+                BatchSize::SmallInput
+            );
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_new_from_strings,
@@ -186,6 +319,8 @@ criterion_group!(
     bench_new_from_iter,
     bench_construction_comparison,
     bench_large_strings,
+    bench_packed,
+    bench_small,
 );
 
 criterion_main!(benches);
@@ -4,64 +4,65 @@
 //! Environment variables example.
 //!
 //! This example demonstrates:
-//! - Creating CStringArray from environment variables
-//! - Formatting strings for C-style environment (KEY=VALUE)
+//! - Creating an EnvArray from environment variables
+//! - Looking up, setting and removing variables by key
 //! - Passing environment to child processes via FFI
 //! - Filtering and transforming data
 
-use cstring_array::CStringArray;
+use cstring_array::EnvArray;
 
 fn main() {
     println!("Environment Variables Example\n");
 
-    let env_vars: Vec<String> = std::env::vars()
-        .map(|(key, value)| format!("{}={}", key, value))
-        .collect();
+    let env = EnvArray::from_pairs(std::env::vars()).expect("Failed to create EnvArray");
 
-    println!("Total environment variables: {}\n", env_vars.len());
+    println!("Total environment variables: {}\n", env.len());
 
     println!("First 10 environment variables:");
-    for (i, var) in env_vars.iter().take(10).enumerate() {
-        println!("  [{}] {}", i, var);
+    for (i, (key, value)) in env.iter().take(10).enumerate() {
+        println!(
+            "  [{}] {}={}",
+            i,
+            String::from_utf8_lossy(key),
+            value.to_string_lossy()
+        );
     }
 
-    println!("\nCreating CStringArray from environment...");
-    let env_array = CStringArray::new(env_vars).expect("Failed to create CStringArray");
+    println!("\nEnvironment block:");
+    println!("  Length: {}", env.len());
+    println!("  Pointer: {:p}", env.as_envp());
 
-    println!("Array created successfully:");
-    println!("  Length: {}", env_array.len());
-    println!("  Pointer: {:p}", env_array.as_ptr());
-
-    println!("\nSearching for PATH variable:");
-    for env in env_array.iter() {
-        let env_str = env.to_str().unwrap();
-        if env_str.starts_with("PATH=") {
-            println!("  Found: {}", env_str);
-            break;
-        }
+    println!("\nLooking up PATH variable:");
+    if let Some(path) = env.get("PATH") {
+        println!("  Found: PATH={}", path.to_string_lossy());
     }
 
     println!("\nFiltering variables with prefix:");
     let prefix = "CARGO_";
     println!("Variables starting with '{}':", prefix);
-    for env in env_array.iter() {
-        let env_str = env.to_str().unwrap();
-        if env_str.starts_with(prefix) {
-            println!("  {}", env_str);
+    for (key, value) in env.iter() {
+        if key.starts_with(prefix.as_bytes()) {
+            println!(
+                "  {}={}",
+                String::from_utf8_lossy(key),
+                value.to_string_lossy()
+            );
         }
     }
 
     println!("\nCreating filtered environment:");
-    let filtered: Vec<String> = std::env::vars()
-        .filter(|(key, _)| key.starts_with("CARGO_") || key == "PATH" || key == "HOME")
-        .map(|(key, value)| format!("{}={}", key, value))
-        .collect();
+    let mut filtered = EnvArray::from_pairs(
+        std::env::vars()
+            .filter(|(key, _)| key.starts_with("CARGO_") || key == "PATH" || key == "HOME")
+    )
+    .expect("Failed to create filtered environment");
+    filtered.set("LANG", "C").expect("Invalid variable");
+    filtered.remove("HOME");
 
-    let filtered_env = CStringArray::new(filtered).expect("Failed to create filtered array");
-    println!("Filtered environment has {} variables:", filtered_env.len());
-    for env in filtered_env.iter() {
-        println!("  {}", env.to_str().unwrap());
+    println!("Filtered environment has {} variables:", filtered.len());
+    for entry in filtered.as_array() {
+        println!("  {}", entry.to_string_lossy());
     }
 
-    println!("\nThis array can be passed to execve() or similar C functions.");
+    println!("\nThis environment can be passed to execve() or similar C functions.");
 }
@@ -2,13 +2,27 @@
 //
 // SPDX-License-Identifier: MIT
 
-use std::{
-    ffi::{CString, c_char},
+use alloc::{
+    ffi::CString,
+    string::String,
+    vec::{self, Vec}
+};
+use core::{
+    ffi::{CStr, c_char},
+    iter::FusedIterator,
+    mem,
+    ops::{Bound, RangeBounds},
     ptr::null,
     slice::Iter
 };
+#[cfg(all(unix, feature = "std"))]
+use std::ffi::OsString;
 
-use crate::error::{CStringArrayError, CStringArrayError::EmptyArray};
+use crate::{
+    convert::IntoCString,
+    error::{CStringArrayError, CStringArrayError::EmptyArray, RecoverableError},
+    sanitize::NulPolicy
+};
 
 /// Safe wrapper for passing string arrays to C FFI as `char**`.
 ///
@@ -58,8 +72,8 @@ impl CStringArray {
     /// # Errors
     ///
     /// Returns `CStringArrayError::NulError` if any string contains an interior
-    /// null byte. Returns `CStringArrayError::EmptyArray` if the input
-    /// vector is empty.
+    /// null byte; the error reports the index of the first offending string.
+    /// Returns `CStringArrayError::EmptyArray` if the input vector is empty.
     ///
     /// # Example
     ///
@@ -75,19 +89,142 @@ impl CStringArray {
             return Err(EmptyArray);
         }
 
+        Self::new_allow_empty(strings)
+    }
+
+    /// Creates a new `CStringArray` from a vector of strings, accepting an
+    /// empty vector.
+    ///
+    /// An empty input produces a pointer table that holds only the null
+    /// terminator, which is what `execve` expects for an empty environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if any string contains an interior
+    /// null byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let envp = CStringArray::new_allow_empty(Vec::new()).unwrap();
+    /// assert!(envp.is_empty());
+    /// assert!(unsafe { (*envp.as_ptr()).is_null() });
+    /// ```
+    pub fn new_allow_empty(strings: Vec<String>) -> Result<Self, CStringArrayError> {
         let cstrings: Vec<CString> = strings
             .into_iter()
-            .map(CString::new)
+            .enumerate()
+            .map(|(i, s)| CString::new(s).map_err(|e| CStringArrayError::nul_at(i, e)))
             .collect::<Result<_, _>>()?;
 
-        let mut pointers: Vec<*const c_char> = Vec::with_capacity(cstrings.len() + 1);
-        pointers.extend(cstrings.iter().map(|s| s.as_ptr()));
-        pointers.push(null());
+        Ok(Self::from_cstrings_allow_empty(cstrings))
+    }
 
-        Ok(Self {
-            strings: cstrings,
-            pointers
-        })
+    /// Creates a new `CStringArray` from a vector of strings or byte vectors,
+    /// repairing interior null bytes instead of rejecting them.
+    ///
+    /// Every element containing a null byte is rewritten according to
+    /// `policy`. Returns the array together with the number of elements that
+    /// were changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::EmptyArray` if the input vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, NulPolicy};
+    ///
+    /// let labels = vec!["ok".to_string(), "tr\0uncated".to_string()];
+    /// let (array, changed) = CStringArray::new_lossy(labels, NulPolicy::Truncate).unwrap();
+    /// assert_eq!(changed, 1);
+    /// assert_eq!(array[1].to_str().unwrap(), "tr");
+    /// ```
+    pub fn new_lossy<T>(
+        strings: Vec<T>,
+        policy: NulPolicy
+    ) -> Result<(Self, usize), CStringArrayError>
+    where
+        T: Into<Vec<u8>>
+    {
+        if strings.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        let mut changed = 0;
+        let cstrings: Vec<CString> = strings
+            .into_iter()
+            .map(|s| {
+                let mut bytes = s.into();
+                if policy.apply(&mut bytes) {
+                    changed += 1;
+                }
+                // SAFETY: the policy removed or replaced every null byte.
+                unsafe { CString::from_vec_unchecked(bytes) }
+            })
+            .collect();
+
+        Ok((Self::from_cstrings_allow_empty(cstrings), changed))
+    }
+
+    /// Creates a new `CStringArray` from a vector of strings or byte vectors,
+    /// handing the vector back if construction fails.
+    ///
+    /// Every element is validated before any of them is converted, so on
+    /// failure the returned [`RecoverableError`] holds the input exactly as it
+    /// was passed in. The caller can fix or remove the element at
+    /// [`RecoverableError::index`] and retry without cloning the input
+    /// beforehand. On success each element's buffer is reused for its
+    /// `CString`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RecoverableError`] wrapping `CStringArrayError::NulError`
+    /// if any element contains an interior null byte, or
+    /// `CStringArrayError::EmptyArray` if the input vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let envp = vec!["HOME=/root".to_string(), "BAD=x\0y".to_string()];
+    /// let err = CStringArray::new_recoverable(envp).unwrap_err();
+    ///
+    /// let mut envp = err.into_inner();
+    /// envp[1] = envp[1].replace('\0', "");
+    ///
+    /// let array = CStringArray::new_recoverable(envp).unwrap();
+    /// assert_eq!(array[1].to_str().unwrap(), "BAD=xy");
+    /// ```
+    pub fn new_recoverable<T>(strings: Vec<T>) -> Result<Self, RecoverableError<T>>
+    where
+        T: AsRef<[u8]> + Into<Vec<u8>>
+    {
+        if strings.is_empty() {
+            return Err(RecoverableError::new(EmptyArray, strings));
+        }
+
+        let invalid = strings.iter().position(|s| s.as_ref().contains(&0));
+        if let Some(index) = invalid {
+            let error =
+                CString::new(strings[index].as_ref()).expect_err("element contains a null byte");
+            return Err(RecoverableError::new(
+                CStringArrayError::nul_at(index, error),
+                strings
+            ));
+        }
+
+        let cstrings: Vec<CString> = strings
+            .into_iter()
+            // SAFETY: every element was checked above to contain no null byte.
+            .map(|s| unsafe { CString::from_vec_unchecked(s.into()) })
+            .collect();
+
+        Ok(Self::from_cstrings_allow_empty(cstrings))
     }
 
     /// Creates a new `CStringArray` from a vector of `CString`s (zero-copy).
@@ -122,14 +259,231 @@ impl CStringArray {
             return Err(EmptyArray);
         }
 
+        Ok(Self::from_cstrings_allow_empty(strings))
+    }
+
+    /// Creates a new `CStringArray` from a vector of byte vectors.
+    ///
+    /// Each vector is reused as the storage of its `CString`; a null
+    /// terminator is appended to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if any vector contains a null
+    /// byte. Returns `CStringArrayError::EmptyArray` if the input vector is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let array = CStringArray::from_bytes_vec(vec![b"cmdline".to_vec(), vec![0xff, 0x01]]).unwrap();
+    /// assert_eq!(array[1].as_bytes(), &[0xff, 0x01]);
+    /// ```
+    pub fn from_bytes_vec(strings: Vec<Vec<u8>>) -> Result<Self, CStringArrayError> {
+        if strings.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        let cstrings: Vec<CString> = strings
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| CString::new(s).map_err(|e| CStringArrayError::nul_at(i, e)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_cstrings_allow_empty(cstrings))
+    }
+
+    /// Creates a new `CStringArray` from byte vectors that already end with a
+    /// null terminator.
+    ///
+    /// The existing terminator is kept instead of being appended again, so no
+    /// vector is reallocated. This suits data read from sources such as
+    /// `/proc/<pid>/cmdline`, where every entry is already null-terminated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::FromVecWithNulError` if any vector is not
+    /// terminated by a null byte or contains an interior null byte. Returns
+    /// `CStringArrayError::EmptyArray` if the input vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let array = CStringArray::from_vec_with_nul(vec![b"ls\0".to_vec(), b"-l\0".to_vec()]).unwrap();
+    /// assert_eq!(array[1].to_str().unwrap(), "-l");
+    ///
+    /// assert!(CStringArray::from_vec_with_nul(vec![b"missing".to_vec()]).is_err());
+    /// ```
+    pub fn from_vec_with_nul(strings: Vec<Vec<u8>>) -> Result<Self, CStringArrayError> {
+        if strings.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        let cstrings: Vec<CString> = strings
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| {
+                CString::from_vec_with_nul(s)
+                    .map_err(|e| CStringArrayError::from_vec_with_nul_at(i, e))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_cstrings_allow_empty(cstrings))
+    }
+
+    /// Creates a new `CStringArray` from OS strings or paths without going
+    /// through UTF-8.
+    ///
+    /// The raw Unix bytes of every item are used as-is, so file names and
+    /// environment values that are not valid UTF-8 are passed through
+    /// losslessly. Accepts any iterator of `OsString`, `&OsStr`, `PathBuf` or
+    /// `&Path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if any item contains an interior
+    /// null byte. Returns `CStringArrayError::EmptyArray` if the iterator is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let name = OsStr::from_bytes(b"caf\xe9.txt");
+    /// let array = CStringArray::from_os_strings([OsStr::new("cat"), name]).unwrap();
+    /// assert_eq!(array[1].as_bytes(), b"caf\xe9.txt");
+    ///
+    /// let paths = vec![PathBuf::from("/bin/ls"), PathBuf::from("/tmp")];
+    /// let array = CStringArray::from_os_strings(paths).unwrap();
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    #[cfg(all(unix, feature = "std"))]
+    pub fn from_os_strings<I>(strings: I) -> Result<Self, CStringArrayError>
+    where
+        I: IntoIterator,
+        I::Item: Into<OsString>
+    {
+        Self::try_from_iter(strings.into_iter().map(Into::into))
+    }
+
+    /// Creates a new `CStringArray` from any iterator of values convertible
+    /// into C strings.
+    ///
+    /// This is the fallible counterpart of `collect()`: it accepts `String`,
+    /// `&str`, `CString`, `Vec<u8>`, `&[u8]` and, on Unix, `OsString` and
+    /// `PathBuf` items (see [`IntoCString`]), and reports errors instead of
+    /// panicking. Both internal vectors are preallocated from the iterator's
+    /// `size_hint`, and the input is consumed in a single pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if any item contains an interior
+    /// null byte. Returns `CStringArrayError::EmptyArray` if the iterator
+    /// yields no items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let array = CStringArray::try_from_iter((1..=3).map(|i| format!("arg{}", i))).unwrap();
+    /// assert_eq!(array.len(), 3);
+    ///
+    /// assert!(CStringArray::try_from_iter(["ok", "bad\0"]).is_err());
+    /// assert!(CStringArray::try_from_iter(Vec::<String>::new()).is_err());
+    /// ```
+    pub fn try_from_iter<I, T>(iter: I) -> Result<Self, CStringArrayError>
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoCString
+    {
+        let iter = iter.into_iter();
+        let mut array = Self::with_capacity(iter.size_hint().0);
+        for (i, item) in iter.enumerate() {
+            let value = item
+                .into_c_string()
+                .map_err(|e| CStringArrayError::nul_at(i, e))?;
+            array.push(value);
+        }
+
+        if array.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        Ok(array)
+    }
+
+    /// Creates a new `CStringArray` from a vector of `CString`s (zero-copy),
+    /// accepting an empty vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let array = CStringArray::from_cstrings_allow_empty(Vec::new());
+    /// assert!(array.is_empty());
+    /// ```
+    #[must_use]
+    pub fn from_cstrings_allow_empty(strings: Vec<CString>) -> Self {
         let mut pointers: Vec<*const c_char> = Vec::with_capacity(strings.len() + 1);
         pointers.extend(strings.iter().map(|s| s.as_ptr()));
         pointers.push(null());
 
-        Ok(Self {
+        Self {
             strings,
             pointers
-        })
+        }
+    }
+
+    /// Creates an empty `CStringArray` whose pointer table holds only the null
+    /// terminator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let array = CStringArray::empty();
+    /// assert_eq!(array.len(), 0);
+    /// assert!(unsafe { (*array.as_ptr()).is_null() });
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty `CStringArray` with room for at least `capacity`
+    /// strings before reallocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::with_capacity(2);
+    /// array.push(CString::new("prog").unwrap());
+    /// assert_eq!(array.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut pointers: Vec<*const c_char> = Vec::with_capacity(capacity + 1);
+        pointers.push(null());
+
+        Self {
+            strings: Vec::with_capacity(capacity),
+            pointers
+        }
     }
 
     /// Returns a pointer suitable for passing to C functions expecting
@@ -208,17 +562,22 @@ impl CStringArray {
 
     /// Returns `true` if the array contains no strings.
     ///
-    /// Note: Due to the constructor constraints, this will always return
-    /// `false` for successfully constructed instances, but is provided for
-    /// completeness.
+    /// [`new`](Self::new) and [`from_cstrings`](Self::from_cstrings) reject
+    /// empty input, but empty arrays can be created explicitly with
+    /// [`empty`](Self::empty) or [`new_allow_empty`](Self::new_allow_empty),
+    /// or by removing every element. The pointer table then holds only the
+    /// null terminator.
     ///
     /// # Example
     ///
     /// ```
     /// use cstring_array::CStringArray;
     ///
-    /// let array = CStringArray::new(vec!["x".to_string()]).unwrap();
+    /// let mut array = CStringArray::new(vec!["x".to_string()]).unwrap();
     /// assert!(!array.is_empty());
+    ///
+    /// array.clear();
+    /// assert!(array.is_empty());
     /// ```
     #[inline]
     #[must_use]
@@ -299,7 +658,368 @@ impl CStringArray {
     #[inline]
     #[must_use]
     pub fn into_strings(mut self) -> Vec<CString> {
-        std::mem::take(&mut self.strings)
+        core::mem::take(&mut self.strings)
+    }
+
+    /// Appends a string to the end of the array.
+    ///
+    /// The pointer table is updated in place, so [`as_ptr`](Self::as_ptr)
+    /// returns a valid null-terminated array immediately after the call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::new(vec!["gcc".to_string()]).unwrap();
+    /// array.push(CString::new("-O2").unwrap());
+    /// assert_eq!(array.len(), 2);
+    /// assert_eq!(array[1].to_str().unwrap(), "-O2");
+    /// ```
+    pub fn push(&mut self, value: CString) {
+        let len = self.strings.len();
+        self.pointers[len] = value.as_ptr();
+        self.pointers.push(null());
+        self.strings.push(value);
+    }
+
+    /// Inserts a string at position `index`, shifting all elements after it
+    /// to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["gcc", "main.c"]).unwrap();
+    /// array.insert(1, CString::new("-Wall").unwrap());
+    /// assert_eq!(array[1].to_str().unwrap(), "-Wall");
+    /// assert_eq!(array[2].to_str().unwrap(), "main.c");
+    /// ```
+    pub fn insert(&mut self, index: usize, value: CString) {
+        let ptr = value.as_ptr();
+        self.strings.insert(index, value);
+        self.pointers.insert(index, ptr);
+    }
+
+    /// Removes and returns the string at position `index`, shifting all
+    /// elements after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "b", "c"]).unwrap();
+    /// let removed = array.remove(1);
+    /// assert_eq!(removed.to_str().unwrap(), "b");
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> CString {
+        let value = self.strings.remove(index);
+        self.pointers.remove(index);
+        value
+    }
+
+    /// Replaces the string at position `index` and returns the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub(crate) fn replace(&mut self, index: usize, value: CString) -> CString {
+        self.pointers[index] = value.as_ptr();
+        mem::replace(&mut self.strings[index], value)
+    }
+
+    /// Removes the last string and returns it, or `None` if the array is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "b"]).unwrap();
+    /// assert_eq!(array.pop().unwrap().to_str().unwrap(), "b");
+    /// assert_eq!(array.pop().unwrap().to_str().unwrap(), "a");
+    /// assert!(array.pop().is_none());
+    /// ```
+    pub fn pop(&mut self) -> Option<CString> {
+        let value = self.strings.pop()?;
+        self.pointers.swap_remove(self.strings.len());
+        Some(value)
+    }
+
+    /// Shortens the array, keeping the first `len` strings and dropping the
+    /// rest.
+    ///
+    /// Has no effect if `len` is greater than or equal to the current length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "b", "c"]).unwrap();
+    /// array.truncate(1);
+    /// assert_eq!(array.len(), 1);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.strings.len() {
+            return;
+        }
+
+        self.pointers.truncate(len);
+        self.pointers.push(null());
+        self.strings.truncate(len);
+    }
+
+    /// Removes all strings, leaving only the null terminator in the pointer
+    /// table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "b"]).unwrap();
+    /// array.clear();
+    /// assert!(array.is_empty());
+    /// assert!(unsafe { (*array.as_ptr()).is_null() });
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Reserves capacity for at least `additional` more strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a"]).unwrap();
+    /// array.reserve(10);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.strings.reserve(additional);
+        self.pointers.reserve(additional);
+    }
+
+    /// Retains only the strings for which the predicate returns `true`,
+    /// preserving their relative order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["PATH=/bin", "LD_PRELOAD=x.so", "HOME=/"]).unwrap();
+    /// array.retain(|s| !s.to_bytes().starts_with(b"LD_"));
+    /// assert_eq!(array.len(), 2);
+    /// assert_eq!(array[1].to_str().unwrap(), "HOME=/");
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&CStr) -> bool
+    {
+        self.strings.retain(|s| f(s));
+        self.rebuild_pointers();
+    }
+
+    /// Sorts the strings in ascending byte order.
+    ///
+    /// The sort is stable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["b", "c", "a"]).unwrap();
+    /// array.sort();
+    /// assert_eq!(array, CStringArray::try_from(["a", "b", "c"]).unwrap());
+    /// ```
+    pub fn sort(&mut self) {
+        self.strings.sort();
+        self.rebuild_pointers();
+    }
+
+    /// Sorts the strings with a key extraction function.
+    ///
+    /// The sort is stable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["ccc", "a", "bb"]).unwrap();
+    /// array.sort_by_key(|s| s.to_bytes().len());
+    /// assert_eq!(array, CStringArray::try_from(["a", "bb", "ccc"]).unwrap());
+    /// ```
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&CStr) -> K
+    {
+        self.strings.sort_by_key(|s| f(s));
+        self.rebuild_pointers();
+    }
+
+    /// Removes consecutive repeated strings.
+    ///
+    /// If the array is sorted, this removes all duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "a", "b", "a"]).unwrap();
+    /// array.dedup();
+    /// assert_eq!(array, CStringArray::try_from(["a", "b", "a"]).unwrap());
+    /// ```
+    pub fn dedup(&mut self) {
+        self.strings.dedup();
+        self.rebuild_pointers();
+    }
+
+    /// Removes consecutive strings that resolve to the same key.
+    ///
+    /// The first string of each run is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["A=1", "A=2", "B=3"]).unwrap();
+    /// array.dedup_by_key(|s| {
+    ///     s.to_bytes()
+    ///         .split(|&b| b == b'=')
+    ///         .next()
+    ///         .map(<[u8]>::to_vec)
+    /// });
+    /// assert_eq!(array, CStringArray::try_from(["A=1", "B=3"]).unwrap());
+    /// ```
+    pub fn dedup_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: PartialEq,
+        F: FnMut(&CStr) -> K
+    {
+        self.strings.dedup_by_key(|s| f(s));
+        self.rebuild_pointers();
+    }
+
+    /// Reverses the order of the strings in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "b", "c"]).unwrap();
+    /// array.reverse();
+    /// assert_eq!(array, CStringArray::try_from(["c", "b", "a"]).unwrap());
+    /// ```
+    pub fn reverse(&mut self) {
+        let len = self.strings.len();
+        self.strings.reverse();
+        self.pointers[..len].reverse();
+    }
+
+    /// Swaps two strings in the array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["a", "b", "c"]).unwrap();
+    /// array.swap(0, 2);
+    /// assert_eq!(array, CStringArray::try_from(["c", "b", "a"]).unwrap());
+    /// ```
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.strings.swap(a, b);
+        self.pointers.swap(a, b);
+    }
+
+    /// Removes the specified range from the array and returns the removed
+    /// strings as an iterator.
+    ///
+    /// Strings not consumed by the iterator are dropped when it is dropped.
+    /// If the iterator is leaked instead, the array keeps only the strings
+    /// before the range, like a leaked [`Vec::drain`], and its pointer table
+    /// stays consistent with them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the
+    /// end point is greater than the length of the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut array = CStringArray::try_from(["prog", "-a", "-b", "file"]).unwrap();
+    /// let flags: Vec<_> = array.drain(1..3).collect();
+    /// assert_eq!(flags.len(), 2);
+    /// assert_eq!(array, CStringArray::try_from(["prog", "file"]).unwrap());
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_>
+    where
+        R: RangeBounds<usize>
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.strings.len()
+        };
+
+        let strings = self.strings.drain(start..end);
+        // Until the iterator is dropped, the strings after the range are not
+        // part of the array, so they are taken out of the table as well.
+        let tail = self.pointers.split_off(end);
+        self.pointers.truncate(start);
+        self.pointers.push(null());
+
+        Drain {
+            strings,
+            pointers: &mut self.pointers,
+            tail
+        }
+    }
+
+    /// Recomputes the pointer table after the strings were reordered.
+    fn rebuild_pointers(&mut self) {
+        self.pointers.clear();
+        self.pointers
+            .extend(self.strings.iter().map(|s| s.as_ptr()));
+        self.pointers.push(null());
     }
 }
 
@@ -311,3 +1031,47 @@ impl Drop for CStringArray {
 
 unsafe impl Send for CStringArray {}
 unsafe impl Sync for CStringArray {}
+
+// ============================================================================
+// Drain
+// ============================================================================
+
+/// Iterator over the strings removed by [`CStringArray::drain`].
+#[derive(Debug)]
+pub struct Drain<'a> {
+    strings:  vec::Drain<'a, CString>,
+    pointers: &'a mut Vec<*const c_char>,
+    /// Pointers to the strings after the drained range, and the terminator.
+    tail:     Vec<*const c_char>
+}
+
+impl Iterator for Drain<'_> {
+    type Item = CString;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.strings.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.strings.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Drain<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.strings.next_back()
+    }
+}
+
+impl ExactSizeIterator for Drain<'_> {}
+
+impl FusedIterator for Drain<'_> {}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        // The strings themselves do not move when the tail is shifted back,
+        // so their pointers are still valid.
+        self.pointers.pop();
+        self.pointers.append(&mut self.tail);
+    }
+}
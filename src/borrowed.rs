@@ -0,0 +1,497 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Borrowed string arrays.
+//!
+//! [`CStrArray`] is the borrowing counterpart of [`CStringArray`]: it does not
+//! own or copy any string data, it only allocates the null-terminated pointer
+//! table over `&CStr` values that live somewhere else, such as `c"..."`
+//! literals.
+//!
+//! [`CStrArrayRef`] covers the opposite direction: a view over a `char**`
+//! received from C, such as `argv` or `envp`, without copying the table or
+//! the strings.
+
+use alloc::{ffi::CString, vec::Vec};
+use core::{
+    ffi::{CStr, c_char},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::Index,
+    ptr::null,
+    slice
+};
+
+use crate::array::CStringArray;
+
+/// Null-terminated `char**` table over borrowed C strings.
+///
+/// Only the pointer table is allocated; the strings themselves are borrowed
+/// for `'a`, so the pointer returned by [`as_ptr`](Self::as_ptr) can never
+/// outlive them.
+///
+/// Unlike [`CStringArray::new`], construction never fails: the inputs are
+/// already valid C strings, and an empty input produces a table holding only
+/// the null terminator.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::CStrArray;
+///
+/// let array = CStrArray::new(&[c"program", c"--verbose"]);
+///
+/// let ptr: *const *const c_char = array.as_ptr();
+/// assert_eq!(array.len(), 2);
+/// assert_eq!(array.get(1), Some(c"--verbose"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CStrArray<'a> {
+    pointers: Vec<*const c_char>,
+    marker:   PhantomData<&'a CStr>
+}
+
+impl<'a> CStrArray<'a> {
+    /// Creates a pointer table over a slice of borrowed C strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStrArray;
+    ///
+    /// let array = CStrArray::new(&[c"a", c"b"]);
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn new(strings: &[&'a CStr]) -> Self {
+        strings.iter().copied().collect()
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `char**`.
+    ///
+    /// The pointer array is null-terminated and valid for the lifetime of
+    /// this `CStrArray`, which cannot outlive the borrowed strings.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.pointers.as_ptr()
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pointers.len() - 1
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string at the specified index, or `None` if the index is
+    /// out of bounds.
+    ///
+    /// The returned reference borrows the original string, not this array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CStr;
+    ///
+    /// use cstring_array::CStrArray;
+    ///
+    /// let first: &'static CStr = CStrArray::new(&[c"first"]).get(0).unwrap();
+    /// assert_eq!(first, c"first");
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&'a CStr> {
+        if index >= self.len() {
+            return None;
+        }
+
+        // SAFETY: every non-terminator pointer was obtained from a `&'a CStr`.
+        Some(unsafe { CStr::from_ptr(self.pointers[index]) })
+    }
+
+    /// Returns an iterator over the borrowed strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStrArray;
+    ///
+    /// let array = CStrArray::new(&[c"a", c"b"]);
+    /// let strings: Vec<_> = array.iter().collect();
+    /// assert_eq!(strings, [c"a", c"b"]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> CStrArrayIter<'_> {
+        CStrArrayIter {
+            pointers: self.pointers[..self.len()].iter()
+        }
+    }
+}
+
+unsafe impl Send for CStrArray<'_> {}
+unsafe impl Sync for CStrArray<'_> {}
+
+/// Borrowed view over a `char**` owned by someone else, typically C code.
+///
+/// Neither the pointer table nor the strings are copied; both are borrowed
+/// for `'a`. Use [`to_owned`](Self::to_owned) to copy them into a
+/// [`CStringArray`].
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::{CStrArrayRef, CStringArray};
+///
+/// // A `char**` as C code would pass it
+/// let source = CStringArray::try_from(["prog", "-v"]).unwrap();
+/// let argv: *const *const c_char = source.as_ptr();
+///
+/// let args = unsafe { CStrArrayRef::from_ptr(argv) };
+/// assert_eq!(args.len(), 2);
+/// assert_eq!(args.get(1), Some(c"-v"));
+///
+/// let owned: CStringArray = args.to_owned();
+/// assert_eq!(owned, source);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CStrArrayRef<'a> {
+    pointers: &'a [*const c_char]
+}
+
+impl<'a> CStrArrayRef<'a> {
+    /// Creates a view over a null-terminated array of C strings, scanning it
+    /// for the terminator.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must be non-null and point to an array of pointers terminated by
+    ///   a null pointer.
+    /// - Every pointer before the terminator must point to a valid
+    ///   null-terminated string.
+    /// - The array and the strings must stay valid and unmodified for `'a`.
+    #[must_use]
+    pub unsafe fn from_ptr(ptr: *const *const c_char) -> Self {
+        let mut len = 0;
+        // SAFETY: the caller guarantees the array is null-terminated.
+        while !unsafe { *ptr.add(len) }.is_null() {
+            len += 1;
+        }
+
+        // SAFETY: the first `len` entries were just read.
+        unsafe { Self::from_ptr_and_len(ptr, len) }
+    }
+
+    /// Creates a view over the first `len` entries of an array of C strings,
+    /// such as the `argc`/`argv` pair passed to `main`.
+    ///
+    /// The array does not need to be null-terminated.
+    ///
+    /// # Safety
+    ///
+    /// - `ptr` must be non-null and valid for reading `len` pointers.
+    /// - Each of those pointers must point to a valid null-terminated string.
+    /// - The array and the strings must stay valid and unmodified for `'a`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStrArrayRef, CStringArray};
+    ///
+    /// let source = CStringArray::try_from(["a", "b", "c"]).unwrap();
+    /// let first_two = unsafe { CStrArrayRef::from_ptr_and_len(source.as_ptr(), 2) };
+    /// assert_eq!(first_two.iter().collect::<Vec<_>>(), [c"a", c"b"]);
+    /// ```
+    #[must_use]
+    pub unsafe fn from_ptr_and_len(ptr: *const *const c_char, len: usize) -> Self {
+        Self {
+            // SAFETY: guaranteed by the caller.
+            pointers: unsafe { slice::from_raw_parts(ptr, len) }
+        }
+    }
+
+    /// Returns the original pointer.
+    ///
+    /// The array behind it is null-terminated only if it was when the view
+    /// was created, which is always the case for [`from_ptr`](Self::from_ptr).
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.pointers.as_ptr()
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pointers.len()
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pointers.is_empty()
+    }
+
+    /// Returns the string at the specified index, or `None` if the index is
+    /// out of bounds.
+    ///
+    /// The returned reference borrows the original string, not this view.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&'a CStr> {
+        // SAFETY: the constructor's caller guarantees every pointer is a
+        // valid C string for `'a`.
+        self.pointers
+            .get(index)
+            .map(|&p| unsafe { CStr::from_ptr(p) })
+    }
+
+    /// Returns an iterator over the borrowed strings.
+    #[inline]
+    pub fn iter(&self) -> CStrArrayIter<'a> {
+        CStrArrayIter {
+            pointers: self.pointers.iter()
+        }
+    }
+
+    /// Copies the strings into an owned [`CStringArray`].
+    #[must_use]
+    pub fn to_owned(&self) -> CStringArray {
+        CStringArray::from_cstrings_allow_empty(self.iter().map(CString::from).collect())
+    }
+}
+
+unsafe impl Send for CStrArrayRef<'_> {}
+unsafe impl Sync for CStrArrayRef<'_> {}
+
+// ============================================================================
+// Iterator
+// ============================================================================
+
+/// Iterator over the strings of a [`CStrArray`] or a [`CStrArrayRef`].
+///
+/// Created by [`CStrArray::iter`] and [`CStrArrayRef::iter`].
+#[derive(Debug, Clone)]
+pub struct CStrArrayIter<'a> {
+    pointers: slice::Iter<'a, *const c_char>
+}
+
+impl<'a> Iterator for CStrArrayIter<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the pointers come from borrowed `&CStr` values, or from a
+        // `CStrArrayRef` whose constructor guarantees them, valid for `'a`.
+        self.pointers.next().map(|&p| unsafe { CStr::from_ptr(p) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pointers.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CStrArrayIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // SAFETY: see `next`.
+        self.pointers
+            .next_back()
+            .map(|&p| unsafe { CStr::from_ptr(p) })
+    }
+}
+
+impl ExactSizeIterator for CStrArrayIter<'_> {}
+
+impl FusedIterator for CStrArrayIter<'_> {}
+
+impl<'s> IntoIterator for &'s CStrArray<'_> {
+    type Item = &'s CStr;
+    type IntoIter = CStrArrayIter<'s>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for CStrArrayRef<'a> {
+    type Item = &'a CStr;
+    type IntoIter = CStrArrayIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &CStrArrayRef<'a> {
+    type Item = &'a CStr;
+    type IntoIter = CStrArrayIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ============================================================================
+// Conversion Traits
+// ============================================================================
+
+impl<'a> FromIterator<&'a CStr> for CStrArray<'a> {
+    fn from_iter<I: IntoIterator<Item = &'a CStr>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut pointers: Vec<*const c_char> = Vec::with_capacity(iter.size_hint().0 + 1);
+        pointers.extend(iter.map(CStr::as_ptr));
+        pointers.push(null());
+
+        Self {
+            pointers,
+            marker: PhantomData
+        }
+    }
+}
+
+impl<'a> From<&'a CStringArray> for CStrArray<'a> {
+    /// Borrows the strings of a [`CStringArray`] without copying them.
+    fn from(array: &'a CStringArray) -> Self {
+        array.iter().map(|s| s.as_c_str()).collect()
+    }
+}
+
+impl PartialEq for CStrArray<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for CStrArray<'_> {}
+
+impl<'a> From<&'a CStringArray> for CStrArrayRef<'a> {
+    /// Views the pointer table of a [`CStringArray`] without copying it.
+    fn from(array: &'a CStringArray) -> Self {
+        // SAFETY: the table holds `len` valid C strings owned by `array`,
+        // which is borrowed for `'a`.
+        unsafe { Self::from_ptr_and_len(array.as_ptr(), array.len()) }
+    }
+}
+
+impl PartialEq for CStrArrayRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for CStrArrayRef<'_> {}
+
+impl Index<usize> for CStrArrayRef<'_> {
+    type Output = CStr;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_builds_null_terminated_table() {
+        let array = CStrArray::new(&[c"prog", c"-v"]);
+        assert_eq!(array.len(), 2);
+
+        let ptr = array.as_ptr();
+        unsafe {
+            assert_eq!(*ptr, c"prog".as_ptr());
+            assert_eq!(*ptr.add(1), c"-v".as_ptr());
+            assert!((*ptr.add(2)).is_null());
+        }
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let array = CStrArray::new(&[]);
+        assert!(array.is_empty());
+        assert!(array.get(0).is_none());
+        assert!(unsafe { (*array.as_ptr()).is_null() });
+    }
+
+    #[test]
+    fn test_get_and_iter() {
+        let array: CStrArray = [c"a", c"b", c"c"].into_iter().collect();
+        assert_eq!(array.get(2), Some(c"c"));
+        assert!(array.get(3).is_none());
+
+        let items: Vec<&CStr> = array.iter().rev().collect();
+        assert_eq!(items, [c"c", c"b", c"a"]);
+        assert_eq!((&array).into_iter().len(), 3);
+    }
+
+    #[test]
+    fn test_from_cstring_array_shares_storage() {
+        let owned = CStringArray::from_cstrings(vec![
+            CString::new("x").unwrap(),
+            CString::new("y").unwrap(),
+        ])
+        .unwrap();
+        let borrowed = CStrArray::from(&owned);
+
+        assert_eq!(borrowed.len(), 2);
+        unsafe {
+            assert_eq!(*borrowed.as_ptr(), owned[0].as_ptr());
+            assert_eq!(*borrowed.as_ptr().add(1), owned[1].as_ptr());
+        }
+        assert_eq!(borrowed, CStrArray::new(&[c"x", c"y"]));
+    }
+
+    #[test]
+    fn test_ref_from_ptr_scans_terminator() {
+        let table = CStrArray::new(&[c"prog", c"", c"arg"]);
+        let view = unsafe { CStrArrayRef::from_ptr(table.as_ptr()) };
+
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.as_ptr(), table.as_ptr());
+        assert_eq!(view.get(1), Some(c""));
+        assert_eq!(view.get(3), None);
+        assert_eq!(&view[2], c"arg");
+        assert_eq!(view.into_iter().collect::<Vec<_>>(), [c"prog", c"", c"arg"]);
+        assert_eq!(
+            view.to_owned(),
+            CStringArray::try_from(["prog", "", "arg"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ref_empty() {
+        let table = CStrArray::new(&[]);
+        let view = unsafe { CStrArrayRef::from_ptr(table.as_ptr()) };
+        assert!(view.is_empty());
+        assert!(view.to_owned().is_empty());
+
+        let pointers = [c"unterminated".as_ptr()];
+        let view = unsafe { CStrArrayRef::from_ptr_and_len(pointers.as_ptr(), 0) };
+        assert!(view.is_empty());
+    }
+
+    #[test]
+    fn test_ref_from_cstring_array() {
+        let owned = CStringArray::try_from(["a", "b"]).unwrap();
+        let view = CStrArrayRef::from(&owned);
+        assert_eq!(view.len(), 2);
+        assert_eq!(view, unsafe { CStrArrayRef::from_ptr(owned.as_ptr()) });
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Conversions between [`Command`] and raw `argv`/`envp` arrays.
+//!
+//! A command assembled with [`Command`] can be turned into the
+//! [`CStringArray`] and [`EnvArray`] a C spawner expects, and arrays received
+//! from C can be turned back into a [`Command`].
+
+use std::{ffi::OsStr, iter::once, os::unix::ffi::OsStrExt, process::Command};
+
+use crate::{
+    array::CStringArray,
+    env::EnvArray,
+    error::{CStringArrayError, CStringArrayError::EmptyArray}
+};
+
+impl CStringArray {
+    /// Builds `argv` for a command: its program followed by its arguments.
+    ///
+    /// An `argv[0]` set with `CommandExt::arg0` cannot be read back from a
+    /// [`Command`], so the first element is always the program.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if the program or an argument
+    /// contains a null byte; the index is the position in `argv`. The
+    /// standard library currently replaces such values with a placeholder
+    /// when they are added, so this does not happen in practice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.args(["-l", "/tmp"]);
+    ///
+    /// let argv = CStringArray::from_command(&command).unwrap();
+    /// assert_eq!(argv, CStringArray::try_from(["ls", "-l", "/tmp"]).unwrap());
+    /// ```
+    pub fn from_command(command: &Command) -> Result<Self, CStringArrayError> {
+        Self::try_from_iter(once(command.get_program()).chain(command.get_args()))
+    }
+
+    /// Builds a [`Command`] from this array used as `argv`, and an optional
+    /// environment.
+    ///
+    /// The first element is the program and the rest are its arguments. With
+    /// `Some(env)`, the command gets exactly that environment; with `None`,
+    /// it inherits the environment of the current process.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::EmptyArray` if the array is empty, since
+    /// there is no program to run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, EnvArray};
+    ///
+    /// let argv = CStringArray::try_from(["env"]).unwrap();
+    /// let env = EnvArray::from_pairs([("GREETING", "hello")]).unwrap();
+    ///
+    /// let output = argv.to_command(Some(&env)).unwrap().output().unwrap();
+    /// assert_eq!(output.stdout, b"GREETING=hello\n");
+    /// ```
+    pub fn to_command(&self, env: Option<&EnvArray>) -> Result<Command, CStringArrayError> {
+        let (program, args) = self.as_slice().split_first().ok_or(EmptyArray)?;
+
+        let mut command = Command::new(OsStr::from_bytes(program.as_bytes()));
+        command.args(args.iter().map(|arg| OsStr::from_bytes(arg.as_bytes())));
+        if let Some(env) = env {
+            command.env_clear();
+            command.envs(env.iter().map(|(key, value)| {
+                (OsStr::from_bytes(key), OsStr::from_bytes(value.to_bytes()))
+            }));
+        }
+        Ok(command)
+    }
+}
+
+impl EnvArray {
+    /// Resolves the environment a command would run with: the environment of
+    /// the current process if `inherit` is `true`, or an empty one otherwise,
+    /// with the command's `env` and `env_remove` calls applied on top.
+    ///
+    /// Whether [`Command::env_clear`] was called cannot be read back from a
+    /// [`Command`] on stable Rust, so pass `inherit: false` for a command
+    /// whose environment was cleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable set on the command has an invalid key
+    /// or contains a null byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use cstring_array::EnvArray;
+    ///
+    /// let mut command = Command::new("make");
+    /// command.env("CC", "clang").env_remove("MAKEFLAGS");
+    ///
+    /// let env = EnvArray::from_command(&command, true).unwrap();
+    /// assert_eq!(env.get("CC"), Some(c"clang"));
+    /// assert!(!env.contains_key("MAKEFLAGS"));
+    ///
+    /// command.env_clear().env("CC", "gcc");
+    /// let env = EnvArray::from_command(&command, false).unwrap();
+    /// assert_eq!(env.len(), 1);
+    /// ```
+    pub fn from_command(command: &Command, inherit: bool) -> Result<Self, CStringArrayError> {
+        let mut env = if inherit {
+            Self::from_current_process()
+        } else {
+            Self::new()
+        };
+        env.apply_command_envs(command)?;
+        Ok(env)
+    }
+
+    /// Applies the `env` and `env_remove` calls made on a command to this
+    /// environment.
+    ///
+    /// A call to [`Command::env_clear`] cannot be observed on stable Rust and
+    /// is not applied; start from [`EnvArray::new`] for such commands.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a variable set on the command has an invalid key
+    /// or contains a null byte. Changes applied before the error are kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use cstring_array::EnvArray;
+    ///
+    /// let mut command = Command::new("sh");
+    /// command.env("PATH", "/bin");
+    ///
+    /// let mut env = EnvArray::from_pairs([("HOME", "/root")]).unwrap();
+    /// env.apply_command_envs(&command).unwrap();
+    /// assert_eq!(env.len(), 2);
+    /// ```
+    pub fn apply_command_envs(&mut self, command: &Command) -> Result<(), CStringArrayError> {
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => self.set(key.as_bytes(), value.as_bytes())?,
+                None => {
+                    self.remove(key.as_bytes());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut command = Command::new("prog");
+        command.args(["a", "", "b c"]).env("X", "1").env("Y", "2");
+
+        let argv = CStringArray::from_command(&command).unwrap();
+        let mut env = EnvArray::new();
+        env.apply_command_envs(&command).unwrap();
+
+        let rebuilt = argv.to_command(Some(&env)).unwrap();
+        assert_eq!(rebuilt.get_program(), "prog");
+        assert_eq!(rebuilt.get_args().collect::<Vec<_>>(), ["a", "", "b c"]);
+
+        let mut envs: Vec<_> = rebuilt.get_envs().collect();
+        envs.sort();
+        assert_eq!(
+            envs,
+            [
+                (OsStr::new("X"), Some(OsStr::new("1"))),
+                (OsStr::new("Y"), Some(OsStr::new("2")))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_removals_applied_on_top() {
+        let mut env = EnvArray::from_pairs([("KEEP", "1"), ("DROP", "2")]).unwrap();
+        let mut command = Command::new("prog");
+        command.env_remove("DROP").env("KEEP", "3");
+
+        env.apply_command_envs(&command).unwrap();
+        assert_eq!(env.get("KEEP"), Some(c"3"));
+        assert!(!env.contains_key("DROP"));
+    }
+
+    #[test]
+    fn test_from_command_base() {
+        let mut command = Command::new("prog");
+        command.env("A", "1");
+
+        let env = EnvArray::from_command(&command, false).unwrap();
+        assert_eq!(env.as_array(), &CStringArray::try_from(["A=1"]).unwrap());
+
+        let inherited = EnvArray::from_command(&command, true).unwrap();
+        assert_eq!(inherited.get("A"), Some(c"1"));
+        assert!(inherited.len() >= env.len());
+    }
+
+    #[test]
+    fn test_to_command_without_env_inherits() {
+        let argv = CStringArray::try_from(["true"]).unwrap();
+        let command = argv.to_command(None).unwrap();
+        assert_eq!(command.get_envs().len(), 0);
+        assert!(command.get_args().next().is_none());
+
+        assert!(matches!(
+            CStringArray::empty().to_command(None),
+            Err(EmptyArray)
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Conversion of individual elements into `CString`.
+//!
+//! This module provides the [`IntoCString`] trait used by the generic
+//! constructors such as [`CStringArray::try_from_iter`]. It lets a single
+//! constructor accept strings, byte vectors, C strings and OS strings while
+//! reusing existing allocations where possible.
+//!
+//! [`CStringArray::try_from_iter`]: crate::CStringArray::try_from_iter
+
+use alloc::{
+    borrow::ToOwned,
+    ffi::{CString, NulError},
+    string::String,
+    vec::Vec
+};
+use core::ffi::CStr;
+#[cfg(all(unix, feature = "std"))]
+use std::{
+    ffi::{OsStr, OsString},
+    os::unix::ffi::{OsStrExt, OsStringExt},
+    path::{Path, PathBuf}
+};
+
+/// Conversion of a value into an owned C string.
+///
+/// Owned inputs (`String`, `Vec<u8>`, `OsString`, `PathBuf`) reuse their
+/// buffer, and `CString` is passed through without scanning it again.
+/// Borrowed inputs are copied once.
+///
+/// # Example
+///
+/// ```
+/// use cstring_array::IntoCString;
+///
+/// let s = "hello".into_c_string().unwrap();
+/// assert_eq!(s.to_str().unwrap(), "hello");
+///
+/// assert!(b"a\0b".to_vec().into_c_string().is_err());
+/// ```
+pub trait IntoCString {
+    /// Converts `self` into a `CString`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NulError` if the value contains an interior null byte.
+    fn into_c_string(self) -> Result<CString, NulError>;
+}
+
+impl IntoCString for CString {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        Ok(self)
+    }
+}
+
+impl IntoCString for &CStr {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        Ok(self.to_owned())
+    }
+}
+
+impl IntoCString for String {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self)
+    }
+}
+
+impl IntoCString for &str {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self)
+    }
+}
+
+impl IntoCString for &String {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self.as_str())
+    }
+}
+
+impl IntoCString for Vec<u8> {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self)
+    }
+}
+
+impl IntoCString for &[u8] {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self)
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl IntoCString for OsString {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self.into_vec())
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl IntoCString for &OsStr {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        CString::new(self.as_bytes())
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl IntoCString for PathBuf {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        self.into_os_string().into_c_string()
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl IntoCString for &Path {
+    #[inline]
+    fn into_c_string(self) -> Result<CString, NulError> {
+        self.as_os_str().into_c_string()
+    }
+}
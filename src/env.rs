@@ -0,0 +1,503 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Environment blocks with `KEY=VALUE` semantics.
+//!
+//! [`EnvArray`] stores an environment as `KEY=VALUE` C strings in a
+//! [`CStringArray`], so it can be passed as `envp` directly, while enforcing
+//! the rules C code relies on: keys are non-empty, contain no `=`, and appear
+//! at most once.
+
+use alloc::{
+    collections::{BTreeMap, btree_map::Entry},
+    ffi::CString,
+    vec::Vec
+};
+use core::{
+    ffi::{CStr, c_char},
+    iter::FusedIterator,
+    slice::Iter
+};
+#[cfg(all(unix, feature = "std"))]
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+use crate::{array::CStringArray, error::CStringArrayError};
+
+/// Environment block for passing to C as `envp`.
+///
+/// Each variable is stored as one `KEY=VALUE` C string. Keys are compared
+/// byte-wise, so lookups are case-sensitive as on Unix. Setting an existing
+/// key replaces its value in place: the last write wins.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::EnvArray;
+///
+/// let mut env = EnvArray::new();
+/// env.set("PATH", "/usr/bin:/bin").unwrap();
+/// env.set("LANG", "C").unwrap();
+/// env.set("PATH", "/opt/bin").unwrap();
+///
+/// assert_eq!(env.len(), 2);
+/// assert_eq!(env.get("PATH"), Some(c"/opt/bin"));
+///
+/// // Safe to pass to execve() and similar functions
+/// let envp: *const *const c_char = env.as_envp();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct EnvArray {
+    entries: CStringArray
+}
+
+impl EnvArray {
+    /// Creates an empty environment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an environment from key/value pairs.
+    ///
+    /// Later pairs override earlier ones with the same key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::InvalidKey` if a key is empty or contains
+    /// `=`, and `CStringArrayError::NulError` if a key or value contains a
+    /// null byte. Both report the index of the offending pair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::EnvArray;
+    ///
+    /// let env = EnvArray::from_pairs([("HOME", "/root"), ("TERM", "dumb")]).unwrap();
+    /// assert_eq!(env.get("TERM"), Some(c"dumb"));
+    ///
+    /// let err = EnvArray::from_pairs([("A", "1"), ("B=C", "2")]).unwrap_err();
+    /// assert_eq!(err.index(), Some(1));
+    /// ```
+    pub fn from_pairs<I, K, V>(pairs: I) -> Result<Self, CStringArrayError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>
+    {
+        let entries = pairs
+            .into_iter()
+            .enumerate()
+            .map(|(i, (key, value))| {
+                let key = key.as_ref();
+                Ok((key.len(), entry(key, value.as_ref(), Some(i))?))
+            })
+            .collect::<Result<Vec<_>, CStringArrayError>>()?;
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Creates an environment from OS string pairs, such as the output of
+    /// [`std::env::vars_os`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`from_pairs`](Self::from_pairs).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::EnvArray;
+    ///
+    /// let env = EnvArray::from_vars_os(
+    ///     std::env::vars_os().filter(|(key, _)| key == "PATH" || key == "HOME")
+    /// )
+    /// .unwrap();
+    /// assert!(env.len() <= 2);
+    /// ```
+    #[cfg(all(unix, feature = "std"))]
+    pub fn from_vars_os<I, K, V>(vars: I) -> Result<Self, CStringArrayError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>
+    {
+        let entries = vars
+            .into_iter()
+            .enumerate()
+            .map(|(i, (key, value))| {
+                let key = key.as_ref().as_bytes();
+                Ok((key.len(), entry(key, value.as_ref().as_bytes(), Some(i))?))
+            })
+            .collect::<Result<Vec<_>, CStringArrayError>>()?;
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Captures the environment of the current process.
+    ///
+    /// Variables that cannot be represented, because their key is empty or
+    /// contains `=`, are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::EnvArray;
+    ///
+    /// // SAFETY: the doctest is single-threaded.
+    /// unsafe { std::env::set_var("GREETING", "hello") };
+    ///
+    /// let env = EnvArray::from_current_process();
+    /// assert_eq!(env.get("GREETING"), Some(c"hello"));
+    /// ```
+    #[cfg(all(unix, feature = "std"))]
+    #[must_use]
+    pub fn from_current_process() -> Self {
+        Self::from_entries(std::env::vars_os().filter_map(|(key, value)| {
+            let entry = entry(key.as_bytes(), value.as_bytes(), None).ok()?;
+            Some((key.len(), entry))
+        }))
+    }
+
+    /// Returns the value of `key`, or `None` if it is not set.
+    ///
+    /// Keys that cannot be set, because they are empty or contain `=`, are
+    /// never found.
+    #[must_use]
+    pub fn get<K: AsRef<[u8]>>(&self, key: K) -> Option<&CStr> {
+        let key = key.as_ref();
+        let entry = &self.entries[self.position(key)?];
+        let value = &entry.to_bytes_with_nul()[key.len() + 1..];
+        // SAFETY: the tail of a C string is a C string.
+        Some(unsafe { CStr::from_bytes_with_nul_unchecked(value) })
+    }
+
+    /// Returns `true` if `key` is set.
+    #[must_use]
+    pub fn contains_key<K: AsRef<[u8]>>(&self, key: K) -> bool {
+        self.position(key.as_ref()).is_some()
+    }
+
+    /// Sets `key` to `value`, replacing any previous value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::InvalidKey` if the key is empty or
+    /// contains `=`, and `CStringArrayError::NulError` if the key or value
+    /// contains a null byte. The environment is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArrayError, EnvArray};
+    ///
+    /// let mut env = EnvArray::new();
+    /// env.set("EMPTY", "").unwrap();
+    /// assert_eq!(env.get("EMPTY"), Some(c""));
+    ///
+    /// assert!(matches!(
+    ///     env.set("", "x"),
+    ///     Err(CStringArrayError::InvalidKey { .. })
+    /// ));
+    /// assert!(matches!(
+    ///     env.set("A=B", "x"),
+    ///     Err(CStringArrayError::InvalidKey { .. })
+    /// ));
+    /// ```
+    pub fn set<K, V>(&mut self, key: K, value: V) -> Result<(), CStringArrayError>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>
+    {
+        let key = key.as_ref();
+        let entry = entry(key, value.as_ref(), None)?;
+        self.insert_entry(key.len(), entry);
+        Ok(())
+    }
+
+    /// Removes `key` and returns its value, or `None` if it was not set.
+    ///
+    /// Keys that cannot be set, because they are empty or contain `=`, are
+    /// never found and nothing is removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::EnvArray;
+    ///
+    /// let mut env = EnvArray::from_pairs([("A", "1")]).unwrap();
+    /// assert_eq!(env.remove("A").unwrap().as_c_str(), c"1");
+    /// assert_eq!(env.remove("A"), None);
+    /// ```
+    pub fn remove<K: AsRef<[u8]>>(&mut self, key: K) -> Option<CString> {
+        let key = key.as_ref();
+        let index = self.position(key)?;
+        let mut bytes = self.entries.remove(index).into_bytes();
+        bytes.drain(..=key.len());
+        // SAFETY: the bytes are the tail of a C string without its terminator.
+        Some(unsafe { CString::from_vec_unchecked(bytes) })
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `envp`.
+    ///
+    /// The pointer array is null-terminated and valid for the lifetime of
+    /// this `EnvArray` or until it is modified.
+    #[inline]
+    #[must_use]
+    pub fn as_envp(&self) -> *const *const c_char {
+        self.entries.as_ptr()
+    }
+
+    /// Returns the number of variables.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no variables are set.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over `(key, value)` pairs, in insertion order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::EnvArray;
+    ///
+    /// let env = EnvArray::from_pairs([("A", "1"), ("B", "2")]).unwrap();
+    /// let pairs: Vec<_> = env.iter().collect();
+    /// assert_eq!(pairs, [(&b"A"[..], c"1"), (&b"B"[..], c"2")]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> EnvIter<'_> {
+        EnvIter {
+            entries: self.entries.iter()
+        }
+    }
+
+    /// Returns the underlying array of `KEY=VALUE` strings.
+    #[inline]
+    #[must_use]
+    pub fn as_array(&self) -> &CStringArray {
+        &self.entries
+    }
+
+    /// Consumes the environment and returns the underlying array of
+    /// `KEY=VALUE` strings.
+    #[inline]
+    #[must_use]
+    pub fn into_array(self) -> CStringArray {
+        self.entries
+    }
+
+    /// Builds an environment from validated entries given with the length
+    /// of their key, keeping the position of the first entry and the value
+    /// of the last one for each key.
+    fn from_entries<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, CString)>
+    {
+        let mut strings: Vec<CString> = Vec::new();
+        let mut positions = BTreeMap::new();
+        for (key_len, entry) in entries {
+            match positions.entry(entry.as_bytes()[..key_len].to_vec()) {
+                Entry::Occupied(slot) => strings[*slot.get()] = entry,
+                Entry::Vacant(slot) => {
+                    slot.insert(strings.len());
+                    strings.push(entry);
+                }
+            }
+        }
+
+        Self {
+            entries: CStringArray::from_cstrings_allow_empty(strings)
+        }
+    }
+
+    /// Index of the entry for `key`, if any.
+    fn position(&self, key: &[u8]) -> Option<usize> {
+        if key.is_empty() || key.contains(&b'=') {
+            return None;
+        }
+        self.entries.iter().position(|entry| {
+            let bytes = entry.as_bytes();
+            bytes.len() > key.len() && bytes[key.len()] == b'=' && bytes.starts_with(key)
+        })
+    }
+
+    /// Stores a validated entry, replacing the entry with the same key.
+    fn insert_entry(&mut self, key_len: usize, entry: CString) {
+        match self.position(&entry.as_bytes()[..key_len]) {
+            Some(index) => {
+                self.entries.replace(index, entry);
+            }
+            None => self.entries.push(entry)
+        }
+    }
+}
+
+/// Validates `key` and joins it with `value` into a `KEY=VALUE` C string.
+fn entry(key: &[u8], value: &[u8], index: Option<usize>) -> Result<CString, CStringArrayError> {
+    if key.is_empty() || key.contains(&b'=') {
+        return Err(CStringArrayError::invalid_key(index, key));
+    }
+
+    let mut bytes = Vec::with_capacity(key.len() + value.len() + 2);
+    bytes.extend_from_slice(key);
+    bytes.push(b'=');
+    bytes.extend_from_slice(value);
+    CString::new(bytes).map_err(|e| match index {
+        Some(index) => CStringArrayError::nul_at(index, e),
+        None => e.into()
+    })
+}
+
+// ============================================================================
+// Iterator
+// ============================================================================
+
+/// Iterator over the `(key, value)` pairs of an [`EnvArray`].
+///
+/// Created by [`EnvArray::iter`].
+#[derive(Debug, Clone)]
+pub struct EnvIter<'a> {
+    entries: Iter<'a, CString>
+}
+
+/// Splits a validated `KEY=VALUE` entry.
+fn split(entry: &CStr) -> (&[u8], &CStr) {
+    let bytes = entry.to_bytes_with_nul();
+    let eq = bytes
+        .iter()
+        .position(|&b| b == b'=')
+        .expect("entry contains '='");
+    // SAFETY: the tail of a C string is a C string.
+    (&bytes[..eq], unsafe {
+        CStr::from_bytes_with_nul_unchecked(&bytes[eq + 1..])
+    })
+}
+
+impl<'a> Iterator for EnvIter<'a> {
+    type Item = (&'a [u8], &'a CStr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| split(entry))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for EnvIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(|entry| split(entry))
+    }
+}
+
+impl ExactSizeIterator for EnvIter<'_> {}
+
+impl FusedIterator for EnvIter<'_> {}
+
+impl<'a> IntoIterator for &'a EnvArray {
+    type Item = (&'a [u8], &'a CStr);
+    type IntoIter = EnvIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_write_wins_in_place() {
+        let mut env = EnvArray::from_pairs([("A", "1"), ("B", "2"), ("A", "3")]).unwrap();
+        env.set("B", "4").unwrap();
+
+        let pairs: Vec<_> = env.iter().collect();
+        assert_eq!(pairs, [(&b"A"[..], c"3"), (&b"B"[..], c"4")]);
+
+        let ptr = env.as_envp();
+        unsafe {
+            assert_eq!(CStr::from_ptr(*ptr), c"A=3");
+            assert!((*ptr.add(2)).is_null());
+        }
+    }
+
+    #[test]
+    fn test_key_is_not_prefix_matched() {
+        let env = EnvArray::from_pairs([("PATHEXT", "x"), ("PATH", "/bin")]).unwrap();
+        assert_eq!(env.get("PATH"), Some(c"/bin"));
+        assert_eq!(env.get("PAT"), None);
+        assert!(!env.contains_key("PATHE"));
+    }
+
+    #[test]
+    fn test_invalid_keys_are_never_found() {
+        let mut env = EnvArray::from_pairs([("A", "B=C")]).unwrap();
+        assert_eq!(env.get("A=B"), None);
+        assert_eq!(env.get(""), None);
+        assert!(!env.contains_key("A=B"));
+        assert!(!env.contains_key(""));
+
+        assert_eq!(env.remove("A=B"), None);
+        assert_eq!(env.remove(""), None);
+        assert_eq!(env.get("A"), Some(c"B=C"));
+    }
+
+    #[test]
+    fn test_value_may_contain_equals() {
+        let mut env = EnvArray::new();
+        env.set("OPTS", "a=b=c").unwrap();
+        assert_eq!(env.get("OPTS"), Some(c"a=b=c"));
+        assert_eq!(env.iter().next(), Some((&b"OPTS"[..], c"a=b=c")));
+    }
+
+    #[test]
+    fn test_invalid_input_leaves_env_unchanged() {
+        let mut env = EnvArray::from_pairs([("A", "1")]).unwrap();
+
+        let err = env.set("B", "x\0y").unwrap_err();
+        assert!(matches!(err, CStringArrayError::NulError(_)));
+        let err = env.set("=B", "x").unwrap_err();
+        assert_eq!(err.index(), None);
+        assert!(err.to_string().contains("\"=B\""));
+
+        assert_eq!(env.as_array(), &CStringArray::try_from(["A=1"]).unwrap());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut env = EnvArray::from_pairs([("A", "1"), ("B", "")]).unwrap();
+        assert_eq!(env.remove("B"), Some(CString::default()));
+        assert_eq!(env.remove("B"), None);
+        assert_eq!(env.len(), 1);
+        assert_eq!(env.into_array().len(), 1);
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    #[test]
+    fn test_from_vars_os() {
+        use std::ffi::OsString;
+
+        let env = EnvArray::from_vars_os([
+            (OsString::from("HOME"), OsString::from("/root")),
+            (OsString::from("HOME"), OsString::from("/home/user"))
+        ])
+        .unwrap();
+        assert_eq!(env.get("HOME"), Some(c"/home/user"));
+        assert_eq!(env.len(), 1);
+
+        let err = EnvArray::from_vars_os([("", "x")]).unwrap_err();
+        assert_eq!(err.index(), Some(0));
+    }
+}
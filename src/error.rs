@@ -7,58 +7,447 @@
 //! This module provides the error types used throughout the crate when string
 //! array operations fail. All errors implement the standard `Error` trait for
 //! proper error handling and propagation.
+//!
+//! Errors raised while converting the elements of an input collection record
+//! the index of the offending element and a short escaped preview of its
+//! value, so that a failure in a large environment can be traced back to the
+//! exact entry.
 
-#[cfg(test)]
-use std::ffi::CString;
-use std::{
+#[cfg(feature = "alloc")]
+use alloc::{
+    ffi::{FromVecWithNulError, NulError},
+    string::String,
+    vec::Vec
+};
+use core::{
     error::Error,
-    ffi::NulError,
     fmt::{Display, Formatter, Result as FmtResult}
 };
 
+/// Maximum number of input bytes shown in an error preview.
+#[cfg(feature = "alloc")]
+const PREVIEW_LEN: usize = 32;
+
 /// Error type for CStringArray operations
+#[cfg(feature = "alloc")]
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum CStringArrayError {
     /// String contains an interior null byte
-    NulError(NulError),
+    NulError(InteriorNulError),
+    /// Bytes expected to end with a null terminator are not terminated or
+    /// contain an interior null byte
+    FromVecWithNulError {
+        /// Index of the offending element, if known
+        index: Option<usize>,
+        /// Underlying conversion error
+        error: FromVecWithNulError
+    },
     /// Empty string array is not allowed
-    EmptyArray
+    EmptyArray,
+    /// Environment variable key is empty or contains `=`
+    InvalidKey {
+        /// Index of the offending element, if known
+        index: Option<usize>,
+        /// Escaped preview of the key
+        key:   String
+    },
+    /// Command line cannot be split into shell words
+    ShellSyntax {
+        /// Byte position of the problem in the command line
+        position: usize,
+        /// What is wrong at that position
+        kind:     ShellErrorKind
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CStringArrayError {
+    /// Returns the index of the element that caused the error, if the error
+    /// was raised while converting an element of an input collection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let err = CStringArray::try_from(["ok", "also ok", "b\0ad"]).unwrap_err();
+    /// assert_eq!(err.index(), Some(2));
+    /// ```
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        use CStringArrayError::*;
+        match self {
+            NulError(e) => e.index(),
+            FromVecWithNulError {
+                index, ..
+            } => *index,
+            EmptyArray => None,
+            InvalidKey {
+                index, ..
+            } => *index,
+            ShellSyntax {
+                ..
+            } => None
+        }
+    }
+
+    /// Wraps a `NulError` raised while converting the element at `index`.
+    pub(crate) fn nul_at(index: usize, error: NulError) -> Self {
+        Self::NulError(InteriorNulError::new(Some(index), error))
+    }
+
+    /// Wraps a `FromVecWithNulError` raised while converting the element at
+    /// `index`.
+    pub(crate) fn from_vec_with_nul_at(index: usize, error: FromVecWithNulError) -> Self {
+        Self::FromVecWithNulError {
+            index: Some(index),
+            error
+        }
+    }
+
+    /// Reports an environment variable key that is empty or contains `=`.
+    pub(crate) fn invalid_key(index: Option<usize>, key: &[u8]) -> Self {
+        Self::InvalidKey {
+            index,
+            key: preview(key)
+        }
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl Display for CStringArrayError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         use CStringArrayError::*;
         match self {
-            NulError(e) => {
+            NulError(e) => Display::fmt(e, f),
+            FromVecWithNulError {
+                index,
+                error
+            } => {
+                if let Some(index) = index {
+                    write!(f, "Element {} has invalid null-terminated bytes: ", index)?;
+                } else {
+                    write!(f, "Invalid null-terminated bytes: ")?;
+                }
+                write!(f, "{} (\"{}\")", error, preview(error.as_bytes()))
+            }
+            EmptyArray => write!(f, "Cannot create array from empty input"),
+            InvalidKey {
+                index,
+                key
+            } => {
+                if let Some(index) = index {
+                    write!(f, "Element {} has invalid environment key", index)?;
+                } else {
+                    write!(f, "Invalid environment key")?;
+                }
                 write!(
                     f,
-                    "String contains interior null byte at position {}",
-                    e.nul_position()
+                    " \"{}\": keys must be non-empty and must not contain '='",
+                    key
                 )
             }
-            EmptyArray => write!(f, "Cannot create array from empty input")
+            ShellSyntax {
+                position,
+                kind
+            } => write!(f, "Invalid shell syntax at byte {}: {}", position, kind)
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Error for CStringArrayError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         use CStringArrayError::*;
         match self {
             NulError(e) => Some(e),
-            EmptyArray => None
+            FromVecWithNulError {
+                error, ..
+            } => Some(error),
+            EmptyArray
+            | InvalidKey {
+                ..
+            }
+            | ShellSyntax {
+                ..
+            } => None
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl From<NulError> for CStringArrayError {
     fn from(err: NulError) -> Self {
-        Self::NulError(err)
+        Self::NulError(InteriorNulError::new(None, err))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FromVecWithNulError> for CStringArrayError {
+    fn from(err: FromVecWithNulError) -> Self {
+        Self::FromVecWithNulError {
+            index: None,
+            error: err
+        }
+    }
+}
+
+/// Reason a command line cannot be split into shell words.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShellErrorKind {
+    /// A single quote is never closed
+    UnterminatedSingleQuote,
+    /// A double quote is never closed
+    UnterminatedDoubleQuote,
+    /// The command line ends with a backslash escaping nothing
+    TrailingBackslash,
+    /// The command line contains a null byte
+    NulByte
+}
+
+#[cfg(feature = "alloc")]
+impl Display for ShellErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::UnterminatedSingleQuote => "unterminated single quote",
+            Self::UnterminatedDoubleQuote => "unterminated double quote",
+            Self::TrailingBackslash => "trailing backslash",
+            Self::NulByte => "null byte"
+        })
+    }
+}
+
+/// Interior null byte found in a string, together with where it was found.
+///
+/// Carries the underlying [`NulError`], the index of the offending element
+/// when the error was raised for an element of a collection, and a truncated,
+/// escaped preview of the value for logging.
+///
+/// # Example
+///
+/// ```
+/// use cstring_array::{CStringArray, CStringArrayError};
+///
+/// let args = vec!["cc".to_string(), "-o\0out".to_string()];
+/// match CStringArray::new(args) {
+///     Err(CStringArrayError::NulError(e)) => {
+///         assert_eq!(e.index(), Some(1));
+///         assert_eq!(e.nul_position(), 2);
+///         assert_eq!(e.preview(), "-o\\x00out");
+///     }
+///     _ => panic!("Expected NulError")
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct InteriorNulError {
+    index:        Option<usize>,
+    nul_position: usize,
+    bytes:        Vec<u8>,
+    preview:      String
+}
+
+#[cfg(feature = "alloc")]
+impl InteriorNulError {
+    fn new(index: Option<usize>, error: NulError) -> Self {
+        let nul_position = error.nul_position();
+        let bytes = error.into_vec();
+
+        Self {
+            index,
+            nul_position,
+            preview: preview(&bytes),
+            bytes
+        }
+    }
+
+    /// Returns the index of the offending element, if the error was raised
+    /// while converting an element of an input collection.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the byte position of the first null byte in the value.
+    #[inline]
+    #[must_use]
+    pub fn nul_position(&self) -> usize {
+        self.nul_position
+    }
+
+    /// Returns an escaped preview of the value, truncated to its first 32
+    /// bytes.
+    #[inline]
+    #[must_use]
+    pub fn preview(&self) -> &str {
+        &self.preview
+    }
+
+    /// Consumes the error and returns the offending value.
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for InteriorNulError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(index) = self.index {
+            write!(f, "Element {} contains", index)?;
+        } else {
+            write!(f, "String contains")?;
+        }
+        write!(
+            f,
+            " interior null byte at position {} (\"{}\")",
+            self.nul_position, self.preview
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Error for InteriorNulError {}
+
+/// Construction error that hands the original input back to the caller.
+///
+/// Returned by [`CStringArray::new_recoverable`]. The input vector is returned
+/// unchanged by [`into_inner`](Self::into_inner), so the offending element at
+/// [`index`](Self::index) can be repaired or removed and the construction
+/// retried without cloning the input up front.
+///
+/// [`CStringArray::new_recoverable`]: crate::CStringArray::new_recoverable
+///
+/// # Example
+///
+/// ```
+/// use cstring_array::CStringArray;
+///
+/// let args = vec!["ls".to_string(), "bad\0arg".to_string(), "/tmp".to_string()];
+/// let err = CStringArray::new_recoverable(args).unwrap_err();
+///
+/// let index = err.index().unwrap();
+/// let mut args = err.into_inner();
+/// args.remove(index);
+///
+/// let array = CStringArray::new_recoverable(args).unwrap();
+/// assert_eq!(array.len(), 2);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct RecoverableError<T> {
+    error: CStringArrayError,
+    input: Vec<T>
+}
+
+#[cfg(feature = "alloc")]
+impl<T> RecoverableError<T> {
+    pub(crate) fn new(error: CStringArrayError, input: Vec<T>) -> Self {
+        Self {
+            error,
+            input
+        }
+    }
+
+    /// Returns the underlying error.
+    #[inline]
+    #[must_use]
+    pub fn error(&self) -> &CStringArrayError {
+        &self.error
+    }
+
+    /// Returns the index of the offending element, or `None` if the input
+    /// was empty.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> Option<usize> {
+        self.error.index()
+    }
+
+    /// Returns a reference to the original input.
+    #[inline]
+    #[must_use]
+    pub fn input(&self) -> &[T] {
+        &self.input
+    }
+
+    /// Consumes the error and returns the original input, unchanged.
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.input
+    }
+
+    /// Consumes the error and returns both the underlying error and the
+    /// original input.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (CStringArrayError, Vec<T>) {
+        (self.error, self.input)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Display for RecoverableError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: core::fmt::Debug> Error for RecoverableError<T> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<RecoverableError<T>> for CStringArrayError {
+    fn from(err: RecoverableError<T>) -> Self {
+        err.error
+    }
+}
+
+/// Escapes the first [`PREVIEW_LEN`] bytes of `bytes` for display.
+#[cfg(feature = "alloc")]
+fn preview(bytes: &[u8]) -> String {
+    let mut out: String = bytes
+        .iter()
+        .take(PREVIEW_LEN)
+        .flat_map(|b| b.escape_ascii())
+        .map(char::from)
+        .collect();
+    if bytes.len() > PREVIEW_LEN {
+        out.push_str("...");
+    }
+    out
+}
+
+/// A fixed-capacity array has no room left for another string.
+///
+/// Returned by [`FixedCStringArray::try_push`] when either the string slots
+/// or the byte storage are exhausted. The array is left unchanged.
+///
+/// [`FixedCStringArray::try_push`]: crate::FixedCStringArray::try_push
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Fixed-capacity array is full")
     }
 }
 
-#[cfg(test)]
+impl Error for CapacityError {}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
+    use alloc::ffi::CString;
+
     use super::*;
 
     #[test]
@@ -90,7 +479,7 @@ mod tests {
 
         assert!(err.source().is_some());
         let source = err.source().unwrap();
-        assert!(source.is::<NulError>());
+        assert!(source.is::<InteriorNulError>());
     }
 
     #[test]
@@ -109,6 +498,7 @@ mod tests {
         match err {
             NulError(e) => {
                 assert_eq!(e.nul_position(), 1);
+                assert_eq!(e.into_vec(), b"a\0b");
             }
             _ => panic!("Expected NulError variant")
         }
@@ -126,4 +516,76 @@ mod tests {
         let debug_str2 = format!("{:?}", err2);
         assert!(debug_str2.contains("NulError"));
     }
+
+    #[test]
+    fn test_from_vec_with_nul_error() {
+        let missing = CString::from_vec_with_nul(b"abc".to_vec()).unwrap_err();
+        let err = CStringArrayError::from(missing);
+        assert!(format!("{}", err).contains("not nul terminated"));
+        assert!(err.source().unwrap().is::<FromVecWithNulError>());
+
+        let interior = CString::from_vec_with_nul(b"a\0b\0".to_vec()).unwrap_err();
+        let err = CStringArrayError::from(interior);
+        assert!(format!("{}", err).contains("interior nul byte at pos 1"));
+    }
+
+    #[test]
+    fn test_nul_error_with_index() {
+        let nul_err = CString::new("argv\0value").unwrap_err();
+        let err = CStringArrayError::nul_at(37, nul_err);
+
+        assert_eq!(err.index(), Some(37));
+        assert_eq!(
+            format!("{}", err),
+            "Element 37 contains interior null byte at position 4 (\"argv\\x00value\")"
+        );
+    }
+
+    #[test]
+    fn test_nul_error_without_index() {
+        let err = CStringArrayError::from(CString::new("a\0").unwrap_err());
+        assert_eq!(err.index(), None);
+        assert!(format!("{}", err).starts_with("String contains"));
+    }
+
+    #[test]
+    fn test_preview_is_truncated_and_escaped() {
+        let value = format!("\0{}", "x".repeat(100));
+        let err = match CStringArrayError::nul_at(0, CString::new(value.clone()).unwrap_err()) {
+            CStringArrayError::NulError(e) => e,
+            _ => panic!("Expected NulError")
+        };
+
+        assert_eq!(err.preview(), format!("\\x00{}...", "x".repeat(31)));
+        assert_eq!(err.into_vec(), value.into_bytes());
+
+        assert_eq!(preview(b"tab\tquote\"\xff"), "tab\\tquote\\\"\\xff");
+    }
+
+    #[test]
+    fn test_from_vec_with_nul_error_with_index() {
+        let error = CString::from_vec_with_nul(b"abc".to_vec()).unwrap_err();
+        let err = CStringArrayError::from_vec_with_nul_at(3, error);
+
+        assert_eq!(err.index(), Some(3));
+        let display = format!("{}", err);
+        assert!(display.starts_with("Element 3 has invalid null-terminated bytes"));
+        assert!(display.contains("\"abc\""));
+    }
+
+    #[test]
+    fn test_recoverable_error_parts() {
+        let input = vec!["a".to_string(), "b\0".to_string()];
+        let nul_err = CString::new("b\0").unwrap_err();
+        let err = RecoverableError::new(CStringArrayError::nul_at(1, nul_err), input.clone());
+
+        assert_eq!(err.index(), Some(1));
+        assert_eq!(err.input(), &input[..]);
+        assert_eq!(format!("{}", err), format!("{}", err.error()));
+        assert!(err.source().unwrap().is::<CStringArrayError>());
+
+        let (error, recovered) = err.into_parts();
+        assert_eq!(recovered, input);
+        assert!(matches!(error, CStringArrayError::NulError(_)));
+    }
 }
@@ -0,0 +1,539 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Safe wrappers for the `exec` family of functions.
+//!
+//! Each function takes the argument vector as a [`CStringArray`] and an
+//! optional [`EnvArray`], checks the invariants `exec` relies on, and replaces
+//! the current process image. Like
+//! [`CommandExt::exec`](std::os::unix::process::CommandExt::exec), they only
+//! return on failure, with the error reported by the system.
+//!
+//! When no environment is given, the new program inherits the environment of
+//! the current process.
+//!
+//! Between `fork` and `exec`, a child may only call async-signal-safe
+//! functions, which rules out allocating. [`PreparedExec`] does all the
+//! allocation up front, so that the child only has to call `execve`.
+//!
+//! Requires the `exec` feature.
+
+use std::{
+    env,
+    ffi::{CStr, c_int},
+    fs::File,
+    io::{Error, ErrorKind, Read},
+    os::{
+        fd::{AsRawFd, BorrowedFd, OwnedFd},
+        unix::ffi::OsStrExt
+    }
+};
+
+use crate::{
+    array::CStringArray,
+    env::EnvArray,
+    sys::{check, envp}
+};
+
+/// Executes the program at `path`, replacing the current process.
+///
+/// `path` is used as is: it is not searched for in `PATH`.
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidInput` without calling `execve` if
+/// `argv` is empty, and the system error if `execve` fails.
+///
+/// # Example
+///
+/// ```no_run
+/// use cstring_array::{CStringArray, EnvArray, exec};
+///
+/// let argv = CStringArray::try_from(["ls", "-l"]).unwrap();
+/// let env = EnvArray::from_pairs([("LC_ALL", "C")]).unwrap();
+///
+/// let err = exec::execve(c"/bin/ls", &argv, Some(&env));
+/// eprintln!("exec failed: {}", err);
+/// ```
+pub fn execve(path: &CStr, argv: &CStringArray, env: Option<&EnvArray>) -> Error {
+    if let Err(err) = check(argv, env) {
+        return err;
+    }
+
+    // SAFETY: all pointers are valid, null-terminated arrays of C strings
+    // that outlive the call.
+    unsafe { libc::execve(path.as_ptr(), argv.as_ptr(), envp(env)) };
+    Error::last_os_error()
+}
+
+/// Executes `file`, searching for it in `PATH` when it contains no `/`,
+/// replacing the current process.
+///
+/// With an environment, `execvpe` is used. It is not available on every
+/// platform; where it is missing, passing `Some(env)` fails with an error of
+/// kind `Unsupported`.
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidInput` without calling `exec` if `argv`
+/// is empty, and the system error if the call fails.
+///
+/// # Example
+///
+/// ```no_run
+/// use cstring_array::{CStringArray, exec};
+///
+/// let argv = CStringArray::try_from(["echo", "hello"]).unwrap();
+/// let err = exec::execvp(c"echo", &argv, None);
+/// eprintln!("exec failed: {}", err);
+/// ```
+pub fn execvp(file: &CStr, argv: &CStringArray, env: Option<&EnvArray>) -> Error {
+    if let Err(err) = check(argv, env) {
+        return err;
+    }
+
+    let Some(env) = env else {
+        // SAFETY: see `execve`.
+        unsafe { libc::execvp(file.as_ptr(), argv.as_ptr()) };
+        return Error::last_os_error();
+    };
+    execvpe(file, argv, env)
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn execvpe(file: &CStr, argv: &CStringArray, env: &EnvArray) -> Error {
+    // SAFETY: see `execve`.
+    unsafe { libc::execvpe(file.as_ptr(), argv.as_ptr(), env.as_envp()) };
+    Error::last_os_error()
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn execvpe(_file: &CStr, _argv: &CStringArray, _env: &EnvArray) -> Error {
+    Error::new(ErrorKind::Unsupported, "execvpe is not available")
+}
+
+/// Executes the program referred to by the open file descriptor `fd`,
+/// replacing the current process.
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidInput` without calling `fexecve` if
+/// `argv` is empty, and the system error if `fexecve` fails.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::{fs::File, os::fd::AsFd};
+///
+/// use cstring_array::{CStringArray, exec};
+///
+/// let program = File::open("/bin/true").unwrap();
+/// let argv = CStringArray::try_from(["true"]).unwrap();
+/// let err = exec::fexecve(program.as_fd(), &argv, None);
+/// eprintln!("exec failed: {}", err);
+/// ```
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly"
+))]
+pub fn fexecve(fd: BorrowedFd<'_>, argv: &CStringArray, env: Option<&EnvArray>) -> Error {
+    if let Err(err) = check(argv, env) {
+        return err;
+    }
+
+    // SAFETY: see `execve`; `fd` is an open descriptor.
+    unsafe { libc::fexecve(fd.as_raw_fd(), argv.as_ptr(), envp(env)) };
+    Error::last_os_error()
+}
+
+/// Executes the program at `path`, resolved relative to the directory `dir`
+/// (or to the current directory when `None`), replacing the current process.
+///
+/// `flags` are passed through, for example `libc::AT_EMPTY_PATH` to execute
+/// `dir` itself with an empty `path`.
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidInput` without calling `execveat` if
+/// `argv` is empty, and the system error if `execveat` fails.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::{fs::File, os::fd::AsFd};
+///
+/// use cstring_array::{CStringArray, exec};
+///
+/// let bin = File::open("/usr/bin").unwrap();
+/// let argv = CStringArray::try_from(["env"]).unwrap();
+/// let err = exec::execveat(Some(bin.as_fd()), c"env", &argv, None, 0);
+/// eprintln!("exec failed: {}", err);
+/// ```
+#[cfg(target_os = "linux")]
+pub fn execveat(
+    dir: Option<BorrowedFd<'_>>,
+    path: &CStr,
+    argv: &CStringArray,
+    env: Option<&EnvArray>,
+    flags: libc::c_int
+) -> Error {
+    if let Err(err) = check(argv, env) {
+        return err;
+    }
+
+    let dirfd = dir.map_or(libc::AT_FDCWD, |fd| fd.as_raw_fd());
+    // SAFETY: see `execve`; `dirfd` is an open descriptor or `AT_FDCWD`.
+    // The raw system call is used because the libc wrapper is recent.
+    unsafe {
+        libc::syscall(
+            libc::SYS_execveat,
+            dirfd,
+            path.as_ptr(),
+            argv.as_ptr(),
+            envp(env),
+            flags
+        )
+    };
+    Error::last_os_error()
+}
+
+/// Everything needed to execute a program, prepared before `fork`.
+///
+/// The program is resolved to the list of paths `execvp` would try, using
+/// the `PATH` of the given environment, or of the current process when the
+/// environment does not set it. [`exec`](Self::exec) then only calls
+/// `execve` on each candidate: it does not allocate or take locks, so it can
+/// be called in the child after `fork` or `vfork`.
+///
+/// Unlike `execvp`, a file that the kernel does not recognize as an
+/// executable is not run with `/bin/sh`: `exec` fails with `ENOEXEC`
+/// instead, so scripts need a `#!` line.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(any(target_os = "linux", target_os = "android"))]
+/// # fn main() {
+/// use std::os::fd::{AsFd, FromRawFd, OwnedFd};
+///
+/// use cstring_array::{CStringArray, exec::PreparedExec};
+///
+/// let argv = CStringArray::try_from(["echo", "hello"]).unwrap();
+/// let prepared = PreparedExec::new(c"echo", argv, None).unwrap();
+///
+/// // A pipe whose ends are closed on `exec`.
+/// let mut fds = [0; 2];
+/// assert_eq!(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) }, 0);
+/// let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+///
+/// match unsafe { libc::fork() } {
+///     0 => prepared.exec_and_report(write.as_fd()),
+///     pid => {
+///         drop(write);
+///         PreparedExec::read_report(read).unwrap();
+///         unsafe { libc::waitpid(pid, std::ptr::null_mut(), 0) };
+///     }
+/// }
+/// # }
+/// # #[cfg(not(any(target_os = "linux", target_os = "android")))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone)]
+pub struct PreparedExec {
+    candidates: CStringArray,
+    argv:       CStringArray,
+    env:        Option<EnvArray>
+}
+
+impl PreparedExec {
+    /// Prepares the execution of `program` with `argv` and an optional
+    /// environment.
+    ///
+    /// A `program` containing a `/` is used as is. Otherwise, one candidate
+    /// is built for every directory of `PATH`, which defaults to
+    /// `/bin:/usr/bin` when unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `InvalidInput` if `argv` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, EnvArray, exec::PreparedExec};
+    ///
+    /// let argv = CStringArray::try_from(["ls"]).unwrap();
+    /// let env = EnvArray::from_pairs([("PATH", "/usr/bin:/bin")]).unwrap();
+    ///
+    /// let prepared = PreparedExec::new(c"ls", argv, Some(env)).unwrap();
+    /// assert_eq!(prepared.candidates()[0], *c"/usr/bin/ls");
+    /// assert_eq!(prepared.candidates()[1], *c"/bin/ls");
+    /// ```
+    pub fn new(program: &CStr, argv: CStringArray, env: Option<EnvArray>) -> Result<Self, Error> {
+        check(&argv, env.as_ref())?;
+
+        let program = program.to_bytes();
+        let candidates = if program.contains(&b'/') {
+            CStringArray::try_from_iter([program])
+        } else {
+            let path = env.as_ref().and_then(|env| env.get("PATH"));
+            let inherited = path.is_none().then(|| env::var_os("PATH")).flatten();
+            let path = path
+                .map(CStr::to_bytes)
+                .or(inherited.as_ref().map(|path| path.as_bytes()))
+                .unwrap_or(b"/bin:/usr/bin");
+
+            CStringArray::try_from_iter(path.split(|&b| b == b':').map(|dir| {
+                let mut candidate = dir.to_vec();
+                if !dir.is_empty() {
+                    candidate.push(b'/');
+                }
+                candidate.extend_from_slice(program);
+                candidate
+            }))
+        }
+        .map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+
+        Ok(Self {
+            candidates,
+            argv,
+            env
+        })
+    }
+
+    /// Returns the paths [`exec`](Self::exec) tries, in order.
+    #[must_use]
+    pub fn candidates(&self) -> &CStringArray {
+        &self.candidates
+    }
+
+    /// Executes the prepared program, replacing the current process.
+    ///
+    /// Candidates are tried in order, skipping those that do not exist or
+    /// cannot be executed, like `execvp`. Only `execve` is called, so this is
+    /// async-signal-safe. It only returns on failure, with `EACCES` if a
+    /// candidate was found but not executable, and the last error otherwise.
+    /// A candidate failing with `ENOEXEC` stops the search, without the
+    /// `/bin/sh` fallback of `execvp`.
+    pub fn exec(&self) -> Error {
+        let envp = envp(self.env.as_ref());
+        let mut denied = false;
+        let mut errno = libc::ENOENT;
+
+        for path in &self.candidates {
+            // SAFETY: all pointers are valid, null-terminated arrays of C
+            // strings that outlive the call.
+            unsafe { libc::execve(path.as_ptr(), self.argv.as_ptr(), envp) };
+            errno = Error::last_os_error()
+                .raw_os_error()
+                .unwrap_or(libc::ENOENT);
+            match errno {
+                libc::EACCES => denied = true,
+                libc::ENOENT | libc::ENOTDIR | libc::ELOOP | libc::ENAMETOOLONG => {}
+                _ => return Error::from_raw_os_error(errno)
+            }
+        }
+        Error::from_raw_os_error(if denied { libc::EACCES } else { errno })
+    }
+
+    /// Executes the prepared program, and on failure writes the error number
+    /// to `report` and exits with status 127.
+    ///
+    /// `report` should be the write end of a pipe opened with `O_CLOEXEC`,
+    /// read in the parent with [`read_report`](Self::read_report). Like
+    /// [`exec`](Self::exec), this is async-signal-safe.
+    pub fn exec_and_report(&self, report: BorrowedFd<'_>) -> ! {
+        let bytes = self.exec().raw_os_error().unwrap_or(0).to_ne_bytes();
+
+        let mut written = 0;
+        while written < bytes.len() {
+            // SAFETY: the buffer is valid for the remaining bytes.
+            let ret = unsafe {
+                libc::write(
+                    report.as_raw_fd(),
+                    bytes[written..].as_ptr().cast(),
+                    bytes.len() - written
+                )
+            };
+            match ret {
+                n if n > 0 => written += n.unsigned_abs(),
+                -1 if Error::last_os_error().raw_os_error() == Some(libc::EINTR) => {}
+                _ => break
+            }
+        }
+        // SAFETY: `_exit` is async-signal-safe and skips the parent's
+        // `atexit` handlers.
+        unsafe { libc::_exit(127) }
+    }
+
+    /// Reads the result written by [`exec_and_report`] from the read end of
+    /// the pipe.
+    ///
+    /// Blocks until the child has executed the program or failed, so the
+    /// parent must close its copy of the write end first.
+    ///
+    /// [`exec_and_report`]: Self::exec_and_report
+    ///
+    /// # Errors
+    ///
+    /// Returns the error the child failed with, an error of kind
+    /// `InvalidData` if the report is malformed, and the error of the read
+    /// itself if it fails.
+    pub fn read_report(report: OwnedFd) -> Result<(), Error> {
+        let mut bytes = Vec::new();
+        File::from(report).read_to_end(&mut bytes)?;
+
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let errno = <[u8; size_of::<c_int>()]>::try_from(bytes.as_slice())
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "malformed exec report"))?;
+        Err(Error::from_raw_os_error(c_int::from_ne_bytes(errno)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sys::cloexec_pipe;
+
+    #[test]
+    fn test_empty_argv_is_rejected() {
+        let err = execve(c"/bin/sh", &CStringArray::empty(), None);
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        let err = execvp(c"sh", &CStringArray::empty(), None);
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_missing_program() {
+        let argv = CStringArray::try_from(["missing"]).unwrap();
+        let err = execve(c"/nonexistent/program", &argv, None);
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let err = execvp(c"/nonexistent/program", &argv, None);
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_execve_in_child() {
+        let argv = CStringArray::try_from(["sh", "-c", "exit $CODE"]).unwrap();
+        let env = EnvArray::from_pairs([("CODE", "7")]).unwrap();
+
+        // SAFETY: the child only calls `execve` and `_exit`.
+        let pid = unsafe { libc::fork() };
+        assert!(pid >= 0);
+        if pid == 0 {
+            let _ = execve(c"/bin/sh", &argv, Some(&env));
+            unsafe { libc::_exit(127) };
+        }
+
+        let mut status = 0;
+        assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+        assert!(libc::WIFEXITED(status));
+        assert_eq!(libc::WEXITSTATUS(status), 7);
+    }
+
+    fn fork_prepared(prepared: &PreparedExec) -> (Result<(), Error>, c_int) {
+        use std::os::fd::AsFd;
+
+        let (read, write) = cloexec_pipe();
+        // SAFETY: the child only calls async-signal-safe functions.
+        let pid = unsafe { libc::fork() };
+        assert!(pid >= 0);
+        if pid == 0 {
+            prepared.exec_and_report(write.as_fd());
+        }
+
+        drop(write);
+        let result = PreparedExec::read_report(read);
+        let mut status = 0;
+        assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+        assert!(libc::WIFEXITED(status));
+        (result, libc::WEXITSTATUS(status))
+    }
+
+    #[test]
+    fn test_prepared_candidates() {
+        let argv = CStringArray::try_from(["prog"]).unwrap();
+        let env = EnvArray::from_pairs([("PATH", "/a::/b/")]).unwrap();
+        let prepared = PreparedExec::new(c"prog", argv.clone(), Some(env.clone())).unwrap();
+        assert_eq!(
+            prepared.candidates(),
+            &CStringArray::try_from(["/a/prog", "prog", "/b//prog"]).unwrap()
+        );
+
+        let prepared = PreparedExec::new(c"./prog", argv.clone(), Some(env)).unwrap();
+        assert_eq!(
+            prepared.candidates(),
+            &CStringArray::try_from(["./prog"]).unwrap()
+        );
+
+        let err = PreparedExec::new(c"prog", CStringArray::empty(), None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_prepared_exec_in_child() {
+        let argv = CStringArray::try_from(["sh", "-c", "exit $CODE"]).unwrap();
+        let env =
+            EnvArray::from_pairs([("CODE", "9"), ("PATH", "/nonexistent:/bin:/usr/bin")]).unwrap();
+        let prepared = PreparedExec::new(c"sh", argv, Some(env)).unwrap();
+
+        let (result, status) = fork_prepared(&prepared);
+        assert!(result.is_ok());
+        assert_eq!(status, 9);
+    }
+
+    #[test]
+    fn test_prepared_exec_reports_errno() {
+        let argv = CStringArray::try_from(["missing"]).unwrap();
+        let env = EnvArray::from_pairs([("PATH", "/nonexistent")]).unwrap();
+        let prepared = PreparedExec::new(c"missing-program", argv, Some(env)).unwrap();
+
+        let (result, status) = fork_prepared(&prepared);
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(status, 127);
+    }
+
+    #[test]
+    fn test_prepared_exec_has_no_shell_fallback() {
+        use std::{ffi::CString, fs, os::unix::fs::PermissionsExt};
+
+        let path = env::temp_dir().join(format!("cstring-array-noexec-{}", std::process::id()));
+        fs::write(&path, "exit 3\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let program = CString::new(path.as_os_str().as_bytes()).unwrap();
+        let argv = CStringArray::try_from(["script"]).unwrap();
+        let prepared = PreparedExec::new(&program, argv, None).unwrap();
+
+        // A child forked by another test may briefly hold the script open
+        // for writing, which makes `execve` fail with `ETXTBSY`.
+        let (result, status) = loop {
+            let (result, status) = fork_prepared(&prepared);
+            if result.as_ref().err().and_then(Error::raw_os_error) != Some(libc::ETXTBSY) {
+                break (result, status);
+            }
+        };
+        fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap_err().raw_os_error(), Some(libc::ENOEXEC));
+        assert_eq!(status, 127);
+    }
+}
@@ -0,0 +1,442 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Fixed-capacity string array that never allocates.
+//!
+//! [`FixedCStringArray`] keeps the string bytes, their offsets and the
+//! null-terminated pointer table in const-generic storage inside the value
+//! itself, for targets where heap allocation is not available.
+
+use core::{
+    ffi::{CStr, c_char},
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    ops::Index,
+    ptr::null
+};
+
+use crate::{error::CapacityError, inline::PointerTable};
+
+/// String array with room for up to `COUNT` strings totalling `BYTES` bytes,
+/// null terminators included.
+///
+/// All storage is part of the value, so building and using the array never
+/// touches the heap. [`as_ptr`](Self::as_ptr) produces the same
+/// null-terminated `char**` layout as
+/// [`CStringArray::as_ptr`](crate::CStringArray::as_ptr).
+///
+/// Because the pointer table points into the value's own byte storage, it is
+/// rebuilt by `as_ptr`, which therefore takes `&mut self`. The returned
+/// pointer is valid until the array is moved, modified or dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::FixedCStringArray;
+///
+/// let mut argv = FixedCStringArray::<64, 4>::new();
+/// argv.try_push(c"ls").unwrap();
+/// argv.try_push(c"-l").unwrap();
+///
+/// let ptr: *const *const c_char = argv.as_ptr();
+/// assert_eq!(argv.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedCStringArray<const BYTES: usize, const COUNT: usize> {
+    bytes:   [u8; BYTES],
+    used:    usize,
+    offsets: [usize; COUNT],
+    len:     usize,
+    table:   PointerTable<COUNT>
+}
+
+impl<const BYTES: usize, const COUNT: usize> FixedCStringArray<BYTES, COUNT> {
+    /// Creates an empty array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::FixedCStringArray;
+    ///
+    /// static EMPTY: FixedCStringArray<16, 2> = FixedCStringArray::new();
+    /// assert!(EMPTY.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bytes:   [0; BYTES],
+            used:    0,
+            offsets: [0; COUNT],
+            len:     0,
+            table:   PointerTable::new()
+        }
+    }
+
+    /// Creates an array from C strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CapacityError` if the strings do not fit in `COUNT` slots and
+    /// `BYTES` bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::FixedCStringArray;
+    ///
+    /// let array = FixedCStringArray::<32, 4>::from_cstrs(&[c"a", c"b"]).unwrap();
+    /// assert_eq!(array.len(), 2);
+    ///
+    /// assert!(FixedCStringArray::<32, 1>::from_cstrs(&[c"a", c"b"]).is_err());
+    /// ```
+    pub fn from_cstrs<S: AsRef<CStr>>(strings: &[S]) -> Result<Self, CapacityError> {
+        let mut array = Self::new();
+        for s in strings {
+            array.try_push(s.as_ref())?;
+        }
+        Ok(array)
+    }
+
+    /// Appends a string, copying its bytes and null terminator into the
+    /// array's storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CapacityError` if all `COUNT` slots are used or the string
+    /// does not fit in the remaining bytes. The array is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CapacityError, FixedCStringArray};
+    ///
+    /// let mut array = FixedCStringArray::<8, 4>::new();
+    /// array.try_push(c"hello").unwrap();
+    /// assert_eq!(array.try_push(c"world"), Err(CapacityError));
+    /// assert_eq!(array.len(), 1);
+    /// ```
+    pub fn try_push(&mut self, s: &CStr) -> Result<(), CapacityError> {
+        let bytes = s.to_bytes_with_nul();
+        if self.len == COUNT || bytes.len() > self.remaining_bytes() {
+            return Err(CapacityError);
+        }
+
+        self.bytes[self.used..self.used + bytes.len()].copy_from_slice(bytes);
+        self.offsets[self.len] = self.used;
+        self.used += bytes.len();
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last string, freeing its slot and bytes.
+    ///
+    /// Returns `false` if the array was empty.
+    pub fn pop(&mut self) -> bool {
+        if self.len == 0 {
+            return false;
+        }
+
+        self.len -= 1;
+        self.used = self.offsets[self.len];
+        true
+    }
+
+    /// Removes all strings.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.used = 0;
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `char**`.
+    ///
+    /// The pointer table is rebuilt on every call, so the pointer is valid
+    /// until the array is moved, modified or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CStr;
+    ///
+    /// use cstring_array::FixedCStringArray;
+    ///
+    /// let mut array = FixedCStringArray::<16, 2>::from_cstrs(&[c"a", c"b"]).unwrap();
+    /// let ptr = array.as_ptr();
+    /// unsafe {
+    ///     assert_eq!(CStr::from_ptr(*ptr.add(1)), c"b");
+    ///     assert!((*ptr.add(2)).is_null());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn as_ptr(&mut self) -> *const *const c_char {
+        let base = self.bytes.as_ptr();
+        let slots = self.table.slots_mut();
+        for (slot, &offset) in slots.iter_mut().zip(&self.offsets[..self.len]) {
+            *slot = base.wrapping_add(offset).cast();
+        }
+        if let Some(slot) = slots.get_mut(self.len) {
+            *slot = null();
+        }
+        self.table.as_ptr()
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of strings, `COUNT`.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        COUNT
+    }
+
+    /// Returns the number of unused bytes of storage.
+    ///
+    /// A string of `n` bytes needs `n + 1` bytes including its terminator.
+    #[inline]
+    #[must_use]
+    pub const fn remaining_bytes(&self) -> usize {
+        BYTES - self.used
+    }
+
+    /// Returns the string at the specified index, or `None` if the index is
+    /// out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&CStr> {
+        if index >= self.len {
+            return None;
+        }
+
+        let end = if index + 1 < self.len {
+            self.offsets[index + 1]
+        } else {
+            self.used
+        };
+        let bytes = &self.bytes[self.offsets[index]..end];
+        // SAFETY: `try_push` copied exactly one C string with its terminator
+        // into this range.
+        Some(unsafe { CStr::from_bytes_with_nul_unchecked(bytes) })
+    }
+
+    /// Returns an iterator over the strings.
+    #[inline]
+    pub fn iter(&self) -> FixedIter<'_, BYTES, COUNT> {
+        FixedIter {
+            array: self,
+            front: 0,
+            back:  self.len
+        }
+    }
+
+    /// Returns the used part of the storage, with every string followed by
+    /// its null terminator.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.used]
+    }
+}
+
+// SAFETY: the pointer table only ever points into the array's own storage,
+// which is owned and only mutated through `&mut self`.
+unsafe impl<const BYTES: usize, const COUNT: usize> Send for FixedCStringArray<BYTES, COUNT> {}
+unsafe impl<const BYTES: usize, const COUNT: usize> Sync for FixedCStringArray<BYTES, COUNT> {}
+
+// ============================================================================
+// Iterator
+// ============================================================================
+
+/// Iterator over the strings of a [`FixedCStringArray`].
+///
+/// Created by [`FixedCStringArray::iter`].
+#[derive(Debug, Clone)]
+pub struct FixedIter<'a, const BYTES: usize, const COUNT: usize> {
+    array: &'a FixedCStringArray<BYTES, COUNT>,
+    front: usize,
+    back:  usize
+}
+
+impl<'a, const BYTES: usize, const COUNT: usize> Iterator for FixedIter<'a, BYTES, COUNT> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let item = self.array.get(self.front);
+        self.front += 1;
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<const BYTES: usize, const COUNT: usize> DoubleEndedIterator for FixedIter<'_, BYTES, COUNT> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.array.get(self.back)
+    }
+}
+
+impl<const BYTES: usize, const COUNT: usize> ExactSizeIterator for FixedIter<'_, BYTES, COUNT> {}
+
+impl<const BYTES: usize, const COUNT: usize> FusedIterator for FixedIter<'_, BYTES, COUNT> {}
+
+impl<'a, const BYTES: usize, const COUNT: usize> IntoIterator
+    for &'a FixedCStringArray<BYTES, COUNT>
+{
+    type Item = &'a CStr;
+    type IntoIter = FixedIter<'a, BYTES, COUNT>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ============================================================================
+// Standard Traits
+// ============================================================================
+
+impl<const BYTES: usize, const COUNT: usize> Default for FixedCStringArray<BYTES, COUNT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BYTES: usize, const COUNT: usize> PartialEq for FixedCStringArray<BYTES, COUNT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<const BYTES: usize, const COUNT: usize> Eq for FixedCStringArray<BYTES, COUNT> {}
+
+impl<const BYTES: usize, const COUNT: usize> Hash for FixedCStringArray<BYTES, COUNT> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl<const BYTES: usize, const COUNT: usize> Index<usize> for FixedCStringArray<BYTES, COUNT> {
+    type Output = CStr;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_same_layout_as_cstring_array() {
+        use crate::CStringArray;
+
+        let mut fixed = FixedCStringArray::<64, 4>::from_cstrs(&[c"ls", c"", c"/tmp"]).unwrap();
+        let array = CStringArray::try_from(["ls", "", "/tmp"]).unwrap();
+
+        let fixed_ptr = fixed.as_ptr();
+        let array_ptr = array.as_ptr();
+        for i in 0..=3 {
+            unsafe {
+                let (a, b) = (*fixed_ptr.add(i), *array_ptr.add(i));
+                assert_eq!(a.is_null(), b.is_null());
+                if !a.is_null() {
+                    assert_eq!(CStr::from_ptr(a), CStr::from_ptr(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_capacity_limits() {
+        let mut array = FixedCStringArray::<6, 2>::new();
+        assert_eq!(array.try_push(c"abcdef"), Err(CapacityError));
+        array.try_push(c"ab").unwrap();
+        array.try_push(c"c").unwrap();
+        assert_eq!(array.try_push(c""), Err(CapacityError));
+        assert_eq!(array.remaining_bytes(), 1);
+        assert_eq!(array.as_bytes(), b"ab\0c\0");
+    }
+
+    #[test]
+    fn test_pointer_follows_move() {
+        let mut array = FixedCStringArray::<16, 2>::from_cstrs(&[c"x"]).unwrap();
+        let _ = array.as_ptr();
+
+        let mut moved = Box::new(array.clone());
+        let ptr = moved.as_ptr();
+        unsafe {
+            assert_eq!(CStr::from_ptr(*ptr), c"x");
+            assert!((*ptr.add(1)).is_null());
+        }
+    }
+
+    #[test]
+    fn test_push_after_move() {
+        let mut boxed = Box::new(FixedCStringArray::<16, 3>::from_cstrs(&[c"a"]).unwrap());
+        let _ = boxed.as_ptr();
+
+        let mut moved = *boxed;
+        moved.try_push(c"b").unwrap();
+        let _ = moved.as_ptr();
+        *boxed = moved;
+        boxed.try_push(c"c").unwrap();
+
+        let ptr = boxed.as_ptr();
+        for (i, expected) in [c"a", c"b", c"c"].into_iter().enumerate() {
+            assert_eq!(unsafe { CStr::from_ptr(*ptr.add(i)) }, expected);
+        }
+        assert!(unsafe { (*ptr.add(3)).is_null() });
+    }
+
+    #[test]
+    fn test_pop_and_clear_reset_terminator() {
+        let mut array = FixedCStringArray::<16, 2>::from_cstrs(&[c"a", c"b"]).unwrap();
+        let _ = array.as_ptr();
+
+        assert!(array.pop());
+        assert_eq!(array.remaining_bytes(), 14);
+        assert!(unsafe { (*array.as_ptr().add(1)).is_null() });
+
+        array.clear();
+        assert!(!array.pop());
+        assert!(unsafe { (*array.as_ptr()).is_null() });
+    }
+
+    #[test]
+    fn test_iter_and_equality() {
+        let array = FixedCStringArray::<16, 4>::from_cstrs(&[c"a", c"bc", c"d"]).unwrap();
+        assert_eq!(array.iter().rev().collect::<Vec<_>>(), [c"d", c"bc", c"a"]);
+        assert_eq!(array.iter().len(), 3);
+        assert_eq!(&array[1], c"bc");
+        assert_eq!(array.get(3), None);
+        assert_eq!(array, array.clone());
+    }
+}
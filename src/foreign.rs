@@ -0,0 +1,504 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Owning wrapper for null-terminated string arrays allocated by C code.
+//!
+//! Many C APIs hand back a `char**` that the caller has to free, such as
+//! `g_strsplit`, which null-terminates it, or `backtrace_symbols`, which
+//! returns its length separately. [`ForeignCStringArray`] takes ownership of
+//! such an array, exposes the same read-only API as
+//! [`CStringArray`](crate::CStringArray), and releases it on drop with the
+//! routine described by a [`Deallocator`].
+
+use alloc::boxed::Box;
+use core::{
+    ffi::{CStr, c_char, c_void},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    iter::FusedIterator,
+    ops::Index,
+    ptr::NonNull
+};
+
+/// How a [`ForeignCStringArray`] releases its memory when dropped.
+///
+/// The free functions have the signature of C `free`, so `libc::free`,
+/// `g_free` or a library's own release routine can be passed directly.
+pub enum Deallocator {
+    /// Free every string and then the array itself, as for arrays built with
+    /// one allocation per string (`g_strsplit` with `g_free`)
+    Elements(unsafe extern "C" fn(*mut c_void)),
+    /// Free only the array, as for arrays whose strings live in the same
+    /// allocation (`backtrace_symbols` with `free`, taken over with
+    /// [`ForeignCStringArray::from_raw_parts`])
+    Array(unsafe extern "C" fn(*mut c_void)),
+    /// Call a custom routine with the array pointer, such as `g_strfreev`
+    Custom(Box<dyn FnOnce(*mut *mut c_char) + Send>)
+}
+
+impl Deallocator {
+    /// Creates a [`Deallocator::Custom`] from a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::Deallocator;
+    ///
+    /// let deallocator = Deallocator::custom(|ptr| {
+    ///     // g_strfreev(ptr)
+    ///     let _ = ptr;
+    /// });
+    /// ```
+    pub fn custom<F>(free: F) -> Self
+    where
+        F: FnOnce(*mut *mut c_char) + Send + 'static
+    {
+        Self::Custom(Box::new(free))
+    }
+}
+
+impl Debug for Deallocator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Elements(free) => f.debug_tuple("Elements").field(free).finish(),
+            Self::Array(free) => f.debug_tuple("Array").field(free).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)")
+        }
+    }
+}
+
+/// `char**` allocated by C code and owned by Rust.
+///
+/// The length is computed once when a null-terminated array is taken over
+/// with [`from_raw`](Self::from_raw), or given with
+/// [`from_raw_parts`](Self::from_raw_parts). Dropping the value frees the
+/// array with its [`Deallocator`].
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::{c_char, c_void};
+///
+/// use cstring_array::{Deallocator, ForeignCStringArray};
+///
+/// unsafe extern "C" {
+///     fn malloc(size: usize) -> *mut c_void;
+///     fn free(ptr: *mut c_void);
+///     fn strdup(s: *const c_char) -> *mut c_char;
+/// }
+///
+/// // What a C function returning a freshly allocated `char**` would do
+/// let raw = unsafe {
+///     let table = malloc(3 * size_of::<*mut c_char>()).cast::<*mut c_char>();
+///     *table = strdup(c"hello".as_ptr());
+///     *table.add(1) = strdup(c"world".as_ptr());
+///     *table.add(2) = std::ptr::null_mut();
+///     table
+/// };
+///
+/// let array =
+///     unsafe { ForeignCStringArray::from_raw(raw, Deallocator::Elements(free)) }.unwrap();
+/// assert_eq!(array.len(), 2);
+/// assert_eq!(array.get(1), Some(c"world"));
+/// // Both strings and the table are freed here
+/// ```
+pub struct ForeignCStringArray {
+    ptr:         NonNull<*mut c_char>,
+    len:         usize,
+    deallocator: Option<Deallocator>
+}
+
+impl ForeignCStringArray {
+    /// Takes ownership of a null-terminated array of C strings.
+    ///
+    /// Returns `None` if `ptr` is null, which many C APIs use to report
+    /// failure; the deallocator is then dropped without being called.
+    ///
+    /// # Safety
+    ///
+    /// If `ptr` is not null:
+    ///
+    /// - it must point to an array of pointers terminated by a null pointer,
+    ///   each of which points to a valid null-terminated string;
+    /// - the array and the strings must not be modified or freed by anyone else
+    ///   while the returned value exists;
+    /// - `deallocator` must be the correct way to release them.
+    #[must_use]
+    pub unsafe fn from_raw(ptr: *mut *mut c_char, deallocator: Deallocator) -> Option<Self> {
+        let ptr = NonNull::new(ptr)?;
+
+        let mut len = 0;
+        // SAFETY: the caller guarantees the array is null-terminated.
+        while !unsafe { *ptr.as_ptr().add(len) }.is_null() {
+            len += 1;
+        }
+
+        Some(Self {
+            ptr,
+            len,
+            deallocator: Some(deallocator)
+        })
+    }
+
+    /// Takes ownership of an array of `len` C strings that is not
+    /// necessarily null-terminated, such as the result of
+    /// `backtrace_symbols`.
+    ///
+    /// Returns `None` if `ptr` is null, which many C APIs use to report
+    /// failure; the deallocator is then dropped without being called.
+    ///
+    /// # Safety
+    ///
+    /// If `ptr` is not null:
+    ///
+    /// - it must point to an array of at least `len` pointers, each of which
+    ///   points to a valid null-terminated string;
+    /// - the array and the strings must not be modified or freed by anyone else
+    ///   while the returned value exists;
+    /// - `deallocator` must be the correct way to release them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::ffi::{c_char, c_int, c_void};
+    ///
+    /// use cstring_array::{Deallocator, ForeignCStringArray};
+    ///
+    /// unsafe extern "C" {
+    ///     fn backtrace(buffer: *mut *mut c_void, size: c_int) -> c_int;
+    ///     fn backtrace_symbols(buffer: *const *mut c_void, size: c_int) -> *mut *mut c_char;
+    ///     fn free(ptr: *mut c_void);
+    /// }
+    ///
+    /// let mut frames = [std::ptr::null_mut(); 16];
+    /// let symbols = unsafe {
+    ///     let count = backtrace(frames.as_mut_ptr(), 16);
+    ///     ForeignCStringArray::from_raw_parts(
+    ///         backtrace_symbols(frames.as_ptr(), count),
+    ///         count as usize,
+    ///         Deallocator::Array(free)
+    ///     )
+    /// };
+    /// ```
+    #[must_use]
+    pub unsafe fn from_raw_parts(
+        ptr: *mut *mut c_char,
+        len: usize,
+        deallocator: Deallocator
+    ) -> Option<Self> {
+        Some(Self {
+            ptr: NonNull::new(ptr)?,
+            len,
+            deallocator: Some(deallocator)
+        })
+    }
+
+    /// Releases ownership and returns the raw pointer without freeing
+    /// anything.
+    ///
+    /// The caller becomes responsible for freeing the array.
+    #[must_use]
+    pub fn into_raw(mut self) -> *mut *mut c_char {
+        self.deallocator = None;
+        self.ptr.as_ptr()
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `char**`.
+    ///
+    /// The pointer array is valid for the lifetime of this value. It is
+    /// null-terminated if it was taken over with [`from_raw`](Self::from_raw);
+    /// with [`from_raw_parts`](Self::from_raw_parts), only the first
+    /// [`len`](Self::len) entries may be read.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.ptr.as_ptr().cast_const().cast()
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the string at the specified index, or `None` if the index is
+    /// out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&CStr> {
+        if index >= self.len {
+            return None;
+        }
+
+        // SAFETY: `index` is in bounds and every entry before the terminator
+        // is a valid C string owned by this value.
+        Some(unsafe { CStr::from_ptr(*self.ptr.as_ptr().add(index)) })
+    }
+
+    /// Returns an iterator over the strings.
+    #[inline]
+    pub fn iter(&self) -> ForeignIter<'_> {
+        ForeignIter {
+            array: self,
+            front: 0,
+            back:  self.len
+        }
+    }
+}
+
+impl Drop for ForeignCStringArray {
+    fn drop(&mut self) {
+        let ptr = self.ptr.as_ptr();
+        // SAFETY: the caller of `from_raw` guaranteed that the deallocator
+        // matches how the array was allocated, and nothing else frees it.
+        match self.deallocator.take() {
+            Some(Deallocator::Elements(free)) => unsafe {
+                for i in 0..self.len {
+                    free((*ptr.add(i)).cast());
+                }
+                free(ptr.cast());
+            },
+            Some(Deallocator::Array(free)) => unsafe { free(ptr.cast()) },
+            Some(Deallocator::Custom(free)) => free(ptr),
+            None => {}
+        }
+    }
+}
+
+// SAFETY: the array is exclusively owned, only read through `&self`, and the
+// deallocator is required to be `Send`.
+unsafe impl Send for ForeignCStringArray {}
+unsafe impl Sync for ForeignCStringArray {}
+
+// ============================================================================
+// Iterator
+// ============================================================================
+
+/// Iterator over the strings of a [`ForeignCStringArray`].
+///
+/// Created by [`ForeignCStringArray::iter`].
+#[derive(Debug, Clone)]
+pub struct ForeignIter<'a> {
+    array: &'a ForeignCStringArray,
+    front: usize,
+    back:  usize
+}
+
+impl<'a> Iterator for ForeignIter<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let item = self.array.get(self.front);
+        self.front += 1;
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for ForeignIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.array.get(self.back)
+    }
+}
+
+impl ExactSizeIterator for ForeignIter<'_> {}
+
+impl FusedIterator for ForeignIter<'_> {}
+
+impl<'a> IntoIterator for &'a ForeignCStringArray {
+    type Item = &'a CStr;
+    type IntoIter = ForeignIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ============================================================================
+// Standard Traits
+// ============================================================================
+
+impl Debug for ForeignCStringArray {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ForeignCStringArray")
+            .field("strings", &DebugStrings(self))
+            .field("deallocator", &self.deallocator)
+            .finish()
+    }
+}
+
+/// Formats the strings of an array as a list.
+struct DebugStrings<'a>(&'a ForeignCStringArray);
+
+impl Debug for DebugStrings<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl Index<usize> for ForeignCStringArray {
+    type Output = CStr;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        ffi::CString,
+        ptr::null_mut,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering}
+        }
+    };
+
+    use super::*;
+
+    unsafe extern "C" {
+        fn malloc(size: usize) -> *mut c_void;
+        fn free(ptr: *mut c_void);
+    }
+
+    /// Builds a `malloc`-allocated array with one allocation per string.
+    fn malloc_array(strings: &[&CStr]) -> *mut *mut c_char {
+        unsafe {
+            let table =
+                malloc((strings.len() + 1) * size_of::<*mut c_char>()).cast::<*mut c_char>();
+            for (i, s) in strings.iter().enumerate() {
+                let bytes = s.to_bytes_with_nul();
+                let copy = malloc(bytes.len()).cast::<u8>();
+                copy.copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+                *table.add(i) = copy.cast();
+            }
+            *table.add(strings.len()) = null_mut();
+            table
+        }
+    }
+
+    #[test]
+    fn test_free_elements() {
+        let raw = malloc_array(&[c"a", c"bc", c""]);
+        let array =
+            unsafe { ForeignCStringArray::from_raw(raw, Deallocator::Elements(free)) }.unwrap();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.iter().collect::<Vec<_>>(), [c"a", c"bc", c""]);
+        assert_eq!(array.iter().next_back(), Some(c""));
+        assert_eq!(&array[1], c"bc");
+        assert_eq!(array.get(3), None);
+        assert_eq!(array.as_ptr(), raw.cast_const().cast());
+    }
+
+    #[test]
+    fn test_from_raw_parts_without_terminator() {
+        let strings = [c"one", c"two"];
+        let raw = unsafe {
+            let table = malloc(strings.len() * size_of::<*mut c_char>()).cast::<*mut c_char>();
+            for (i, s) in strings.iter().enumerate() {
+                *table.add(i) = s.as_ptr().cast_mut();
+            }
+            table
+        };
+
+        let array =
+            unsafe { ForeignCStringArray::from_raw_parts(raw, 2, Deallocator::Array(free)) }
+                .unwrap();
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.iter().collect::<Vec<_>>(), strings);
+        assert_eq!(array.get(2), None);
+
+        assert!(
+            unsafe {
+                ForeignCStringArray::from_raw_parts(null_mut(), 0, Deallocator::Array(free))
+            }
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_null_pointer() {
+        let called = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&called);
+        let deallocator = Deallocator::custom(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert!(unsafe { ForeignCStringArray::from_raw(null_mut(), deallocator) }.is_none());
+        assert_eq!(called.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_custom_deallocator_runs_once() {
+        let strings = [CString::new("x").unwrap(), CString::new("y").unwrap()];
+        let mut table = vec![
+            strings[0].as_ptr().cast_mut(),
+            strings[1].as_ptr().cast_mut(),
+            null_mut(),
+        ];
+        let raw = table.as_mut_ptr();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let freed = Arc::new(AtomicUsize::new(0));
+        let (calls_seen, freed_seen) = (Arc::clone(&calls), Arc::clone(&freed));
+        let deallocator = Deallocator::custom(move |ptr| {
+            calls_seen.fetch_add(1, Ordering::SeqCst);
+            freed_seen.store(ptr.addr(), Ordering::SeqCst);
+        });
+
+        let array = unsafe { ForeignCStringArray::from_raw(raw, deallocator) }.unwrap();
+        assert_eq!(array.len(), 2);
+        drop(array);
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(freed.load(Ordering::SeqCst), raw.addr());
+    }
+
+    #[test]
+    fn test_into_raw_skips_deallocator() {
+        let raw = malloc_array(&[c"keep"]);
+        let array = unsafe {
+            ForeignCStringArray::from_raw(raw, Deallocator::custom(|_| panic!("freed")))
+        }
+        .unwrap();
+
+        let back = array.into_raw();
+        assert_eq!(back, raw);
+
+        drop(unsafe { ForeignCStringArray::from_raw(back, Deallocator::Elements(free)) });
+    }
+
+    #[test]
+    fn test_empty_array() {
+        let raw = malloc_array(&[]);
+        let array =
+            unsafe { ForeignCStringArray::from_raw(raw, Deallocator::Array(free)) }.unwrap();
+        assert!(array.is_empty());
+        assert_eq!(array.iter().next(), None);
+    }
+}
@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Fixed-size, null-terminated pointer table stored inline.
+//!
+//! Const generics cannot express `[*const c_char; N + 1]` on stable Rust, so
+//! [`PointerTable`] stores `N` slots followed by a separate terminator field.
+//! With `#[repr(C)]` the terminator immediately follows the last slot, and a
+//! pointer derived from the whole table is valid for all `N + 1` entries.
+
+use core::{ffi::c_char, ptr};
+
+/// `N` pointer slots followed by a permanent null terminator.
+///
+/// Unused slots are kept null, so the table is always null-terminated right
+/// after the last used slot.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PointerTable<const N: usize> {
+    slots:      [*const c_char; N],
+    terminator: *const c_char
+}
+
+impl<const N: usize> PointerTable<N> {
+    /// Creates a table with every slot set to null.
+    pub(crate) const fn new() -> Self {
+        Self {
+            slots:      [ptr::null(); N],
+            terminator: ptr::null()
+        }
+    }
+
+    /// Returns a pointer to the first slot, valid for reading `N + 1`
+    /// entries.
+    #[inline]
+    pub(crate) const fn as_ptr(&self) -> *const *const c_char {
+        ptr::from_ref(self).cast()
+    }
+
+    /// Returns the slots, excluding the terminator.
+    #[inline]
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) const fn slots(&self) -> &[*const c_char; N] {
+        &self.slots
+    }
+
+    /// Returns the slots mutably, excluding the terminator.
+    #[inline]
+    pub(crate) const fn slots_mut(&mut self) -> &mut [*const c_char; N] {
+        &mut self.slots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::{align_of, offset_of, size_of};
+
+    use super::*;
+
+    #[test]
+    fn test_terminator_follows_slots() {
+        assert_eq!(
+            offset_of!(PointerTable<3>, terminator),
+            3 * size_of::<*const c_char>()
+        );
+        assert_eq!(size_of::<PointerTable<3>>(), 4 * size_of::<*const c_char>());
+        assert_eq!(align_of::<PointerTable<3>>(), align_of::<*const c_char>());
+    }
+
+    #[test]
+    fn test_read_through_pointer() {
+        let mut table = PointerTable::<2>::new();
+        let value = c"x".as_ptr();
+        table.slots_mut()[0] = value;
+
+        let ptr = table.as_ptr();
+        unsafe {
+            assert_eq!(*ptr, value);
+            assert!((*ptr.add(1)).is_null());
+            assert!((*ptr.add(2)).is_null());
+        }
+    }
+
+    #[test]
+    fn test_zero_slots() {
+        let table = PointerTable::<0>::new();
+        assert!(table.slots().is_empty());
+        assert!(unsafe { (*table.as_ptr()).is_null() });
+    }
+}
@@ -61,7 +61,177 @@
 //!
 //! // Using FromIterator (collect)
 //! let arr5: CStringArray = vec!["a", "b", "c"].into_iter().map(String::from).collect();
+//!
+//! // Fallible collection from any iterator of strings, bytes or C strings
+//! let arr6 = CStringArray::try_from_iter(vec!["a", "b", "c"]).unwrap();
+//!
+//! // Using the macro (literals are checked at compile time)
+//! let arr7 = cstring_array::cstring_array!["a", "b", "c"];
+//! ```
+//!
+//! # Borrowed Arrays
+//!
+//! [`CStrArray`] builds only the pointer table over strings that already
+//! exist, such as C string literals, and ties the resulting `char**` to their
+//! lifetime:
+//!
+//! ```
+//! use cstring_array::CStrArray;
+//!
+//! let array = CStrArray::new(&[c"ls", c"-l", c"/tmp"]);
+//! assert_eq!(array.len(), 3);
+//! assert_eq!(array.get(0), Some(c"ls"));
+//! ```
+//!
+//! [`CStrArrayRef`] is a view over a `char**` received from C, such as `argv`
+//! in a `main` shim or a callback argument:
+//!
+//! ```
+//! use std::ffi::c_char;
+//!
+//! use cstring_array::{CStrArrayRef, CStringArray};
+//!
+//! unsafe extern "C" fn callback(argv: *const *const c_char) -> usize {
+//!     let args = unsafe { CStrArrayRef::from_ptr(argv) };
+//!     let owned: CStringArray = args.to_owned();
+//!     owned.len()
+//! }
+//! ```
+//!
+//! # Static Arrays
+//!
+//! [`StaticCStrArray`] is built in `const` context from C string literals and
+//! can be stored in a `static` whose pointer stays valid for the whole
+//! program:
+//!
+//! ```
+//! use cstring_array::StaticCStrArray;
+//!
+//! static DEFAULT_ARGS: StaticCStrArray<2> = StaticCStrArray::new([c"--quiet", c"--no-color"]);
+//! assert_eq!(DEFAULT_ARGS.len(), 2);
+//! ```
+//!
+//! # Packed Storage
+//!
+//! [`PackedCStringArray`] stores all strings in one contiguous buffer, which
+//! avoids one heap allocation per string for large arrays:
+//!
+//! ```
+//! use cstring_array::PackedCStringArray;
+//!
+//! let files: Vec<String> = (0..1000).map(|i| format!("src/file_{}.c", i)).collect();
+//! let array = PackedCStringArray::new(&files).unwrap();
+//! assert_eq!(array.len(), 1000);
+//! assert_eq!(array.get(0).unwrap().to_str().unwrap(), "src/file_0.c");
+//! ```
+//!
+//! # Small Arrays
+//!
+//! [`SmallCStringArray`] keeps the pointer table inline for up to `N`
+//! strings, so short argument lists need no separate heap allocation for it:
+//!
+//! ```
+//! use cstring_array::SmallCStringArray;
+//!
+//! let argv = SmallCStringArray::<8>::new(vec!["ls".to_string(), "-la".to_string()]).unwrap();
+//! assert!(argv.is_inline());
+//! ```
+//!
+//! # Fixed-Capacity Arrays
+//!
+//! [`FixedCStringArray`] stores the strings and the pointer table in
+//! const-generic storage and never allocates, for targets without a heap. It
+//! is available even when the `alloc` feature is disabled:
+//!
+//! ```
+//! use cstring_array::FixedCStringArray;
+//!
+//! let mut argv = FixedCStringArray::<64, 4>::new();
+//! argv.try_push(c"ls").unwrap();
+//! assert!(argv.try_push(c"-la").is_ok());
+//! let ptr = argv.as_ptr();
+//! ```
+//!
+//! # Arrays Allocated by C
+//!
+//! [`ForeignCStringArray`] takes ownership of a `char**` returned by a C
+//! library and frees it on drop with the matching [`Deallocator`]. Arrays
+//! that are null-terminated are taken over with
+//! [`ForeignCStringArray::from_raw`], and arrays whose length is returned
+//! separately with [`ForeignCStringArray::from_raw_parts`]:
+//!
+//! ```no_run
+//! use std::ffi::{c_char, c_int, c_void};
+//!
+//! use cstring_array::{Deallocator, ForeignCStringArray};
+//!
+//! unsafe extern "C" {
+//!     fn backtrace(buffer: *mut *mut c_void, size: c_int) -> c_int;
+//!     fn backtrace_symbols(buffer: *const *mut c_void, size: c_int) -> *mut *mut c_char;
+//!     fn free(ptr: *mut c_void);
+//! }
+//!
+//! let mut frames = [std::ptr::null_mut(); 16];
+//! let symbols = unsafe {
+//!     let count = backtrace(frames.as_mut_ptr(), 16);
+//!     ForeignCStringArray::from_raw_parts(
+//!         backtrace_symbols(frames.as_ptr(), count),
+//!         count as usize,
+//!         Deallocator::Array(free)
+//!     )
+//! };
+//! ```
+//!
+//! # Environment Blocks
+//!
+//! [`EnvArray`] stores `KEY=VALUE` strings for `envp`, with one entry per
+//! key and the last write winning:
+//!
+//! ```
+//! use cstring_array::EnvArray;
+//!
+//! let mut env = EnvArray::from_pairs([("PATH", "/usr/bin"), ("LANG", "C")]).unwrap();
+//! env.set("LANG", "en_US.UTF-8").unwrap();
+//! env.remove("PATH");
+//!
+//! assert_eq!(env.get("LANG"), Some(c"en_US.UTF-8"));
+//! let envp = env.as_envp();
+//! ```
+//!
+//! # Commands
+//!
+//! On Unix, `argv` and `envp` can be extracted from a
+//! [`std::process::Command`] with [`CStringArray::from_command`] and
+//! [`EnvArray::from_command`], and turned back into a `Command` with
+//! [`CStringArray::to_command`].
+//!
+//! # Shell Words
+//!
+//! [`CStringArray::from_shell_words`] splits a command line using the POSIX
+//! shell quoting rules, without expansion, and
+//! [`CStringArray::to_shell_string`] quotes an array back into a line that
+//! can be pasted into a shell. [`CStringArray::from_shell_bytes`] and
+//! [`CStringArray::to_shell_bytes`] do the same for lines and elements that
+//! are not valid UTF-8:
+//!
 //! ```
+//! use cstring_array::CStringArray;
+//!
+//! let argv = CStringArray::from_shell_words(r#"git commit -m "first commit""#).unwrap();
+//! assert_eq!(argv.len(), 4);
+//! assert_eq!(
+//!     argv.to_shell_string().unwrap(),
+//!     "git commit -m 'first commit'"
+//! );
+//! ```
+//!
+//! # Giving Arrays to C
+//!
+//! [`CStringArray::leak`] hands out a `'static` `char**` for libraries that
+//! keep `argv` for the life of the process. With the `libc` feature,
+//! `CStringArray::into_raw_malloc` copies the array into `malloc`
+//! allocations that C code can release with `free()`, and
+//! `CStringArray::from_raw_malloc` takes such an array back.
 //!
 //! # Trait Implementations
 //!
@@ -126,13 +296,98 @@
 //! call_c_function(ptr, array.len() as i32);
 //! // array must not be dropped before call_c_function returns
 //! ```
+//!
+//! # `no_std` Support
+//!
+//! The crate only needs `core` and `alloc`. Disable the default `std` feature
+//! and enable `alloc` to use it in `#![no_std]` code:
+//!
+//! ```toml
+//! [dependencies]
+//! cstring-array = { version = "0.2", default-features = false, features = ["alloc"] }
+//! ```
+//!
+//! Without `std`, the conversions from `OsString`, `OsStr` and `PathBuf` are
+//! not available. On targets without a global allocator, leave `alloc`
+//! disabled as well: `FixedCStringArray` and `StaticCStrArray` need neither.
+//!
+//! # Cargo Features
+//!
+//! - `std` (default): conversions from OS strings and paths; enables `alloc`
+//! - `alloc`: every heap-allocating type, including `CStringArray`
+//! - `libc`: `malloc`-compatible `CStringArray::into_raw_malloc` and
+//!   `CStringArray::from_raw_malloc`
+//! - `exec` (Unix): safe `execve`, `execvp`, `fexecve` and `execveat` wrappers
+//!   in the `exec` module, and `PreparedExec` for executing after `fork`
+//! - `spawn` (Unix): a `posix_spawn` builder with file actions and attributes
+//!   in the `spawn` module
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "alloc")]
 mod array;
+#[cfg(feature = "alloc")]
+mod borrowed;
+#[cfg(all(unix, feature = "std"))]
+mod command;
+#[cfg(feature = "alloc")]
+mod convert;
+#[cfg(feature = "alloc")]
+mod env;
 mod error;
+#[cfg(all(unix, feature = "exec"))]
+pub mod exec;
+mod fixed;
+#[cfg(feature = "alloc")]
+mod foreign;
+mod inline;
+#[cfg(feature = "alloc")]
+mod macros;
+#[cfg(feature = "alloc")]
+mod packed;
+#[cfg(feature = "alloc")]
+mod raw;
+#[cfg(feature = "alloc")]
+mod sanitize;
+#[cfg(feature = "alloc")]
+mod shell;
+#[cfg(feature = "alloc")]
+mod small;
+#[cfg(all(unix, feature = "spawn"))]
+pub mod spawn;
+mod statics;
+#[cfg(all(unix, any(feature = "exec", feature = "spawn")))]
+mod sys;
+#[cfg(feature = "alloc")]
 mod traits;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests;
 
-pub use array::CStringArray;
-pub use error::CStringArrayError;
+#[cfg(feature = "alloc")]
+pub use array::{CStringArray, Drain};
+#[cfg(feature = "alloc")]
+pub use borrowed::{CStrArray, CStrArrayIter, CStrArrayRef};
+#[cfg(feature = "alloc")]
+pub use convert::IntoCString;
+#[cfg(feature = "alloc")]
+pub use env::{EnvArray, EnvIter};
+pub use error::CapacityError;
+#[cfg(feature = "alloc")]
+pub use error::{CStringArrayError, InteriorNulError, RecoverableError, ShellErrorKind};
+pub use fixed::{FixedCStringArray, FixedIter};
+#[cfg(feature = "alloc")]
+pub use foreign::{Deallocator, ForeignCStringArray, ForeignIter};
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use macros::__private;
+#[cfg(feature = "alloc")]
+pub use packed::{PackedCStringArray, PackedIter};
+#[cfg(feature = "alloc")]
+pub use sanitize::NulPolicy;
+#[cfg(feature = "alloc")]
+pub use small::SmallCStringArray;
+pub use statics::StaticCStrArray;
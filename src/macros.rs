@@ -0,0 +1,256 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! The [`cstring_array!`](crate::cstring_array) macro and its support code.
+
+/// Creates a [`CStringArray`](crate::CStringArray) from a list of elements,
+/// like `vec!`.
+///
+/// Elements can be literals (`"str"`, `c"str"` or `b"bytes"`) or runtime
+/// expressions of any type that implements
+/// [`IntoCString`](crate::IntoCString) (`String`, `&str`, `CString`,
+/// `Vec<u8>`, ...). Literals are checked for interior null bytes at compile
+/// time.
+///
+/// - If every element is a literal, the macro evaluates to a `CStringArray`
+///   directly, since it cannot fail.
+/// - If at least one element is a runtime expression, it evaluates to
+///   `Result<CStringArray, CStringArrayError>`, reporting the index of the
+///   first element that contains a null byte.
+/// - `cstring_array![]` evaluates to an empty array.
+///
+/// # Example
+///
+/// ```
+/// use cstring_array::cstring_array;
+///
+/// // Only literals: no `Result`
+/// let argv = cstring_array!["ls", "-l", "/tmp"];
+/// assert_eq!(argv.len(), 3);
+///
+/// // C string and byte string literals
+/// let argv = cstring_array![c"grep", b"-e", b"\xff"];
+/// assert_eq!(argv[1], *c"-e");
+///
+/// // Runtime elements: returns `Result`
+/// let level = 3;
+/// let file = String::from("main.c");
+/// let argv = cstring_array!["cc", format!("-O{}", level), file].unwrap();
+/// assert_eq!(argv[1].to_str().unwrap(), "-O3");
+///
+/// let err = cstring_array!["cc", String::from("bad\0arg")].unwrap_err();
+/// assert_eq!(err.index(), Some(1));
+/// ```
+///
+/// A null byte inside a literal is rejected at compile time:
+///
+/// ```compile_fail
+/// use cstring_array::cstring_array;
+///
+/// let argv = cstring_array!["ls", "bad\0literal"];
+/// ```
+///
+/// ```compile_fail
+/// use cstring_array::cstring_array;
+///
+/// let argv = cstring_array![b"bad\0bytes"];
+/// ```
+#[macro_export]
+macro_rules! cstring_array {
+    // Only literals seen so far
+    (@lit [$($acc:tt)*] $lit:literal , $($rest:tt)*) => {
+        $crate::cstring_array!(@lit [$($acc)* $lit,] $($rest)*)
+    };
+    (@lit [$($acc:tt)*] $lit:literal) => {
+        $crate::cstring_array!(@lit [$($acc)* $lit,])
+    };
+    (@lit [$($lit:literal,)*]) => {
+        $crate::__private::from_literals(&[$($crate::cstring_array!(@check $lit)),*])
+    };
+    (@lit [$($lit:literal,)*] $($rest:tt)+) => {
+        $crate::cstring_array!(@rt [$(($crate::cstring_array!(@check $lit)),)*] $($rest)+)
+    };
+
+    // At least one runtime element
+    (@rt [$($acc:tt)*] $lit:literal , $($rest:tt)*) => {
+        $crate::cstring_array!(@rt [$($acc)* ($crate::cstring_array!(@check $lit)),] $($rest)*)
+    };
+    (@rt [$($acc:tt)*] $lit:literal) => {
+        $crate::cstring_array!(@rt [$($acc)* ($crate::cstring_array!(@check $lit)),])
+    };
+    (@rt [$($acc:tt)*] $item:expr , $($rest:tt)*) => {
+        $crate::cstring_array!(@rt [$($acc)* ($item),] $($rest)*)
+    };
+    (@rt [$($acc:tt)*] $item:expr) => {
+        $crate::cstring_array!(@rt [$($acc)* ($item),])
+    };
+    (@rt [$($item:tt,)*]) => {
+        $crate::__private::from_items([$($crate::IntoCString::into_c_string($item)),*])
+    };
+
+    // Compile-time check of a literal, which evaluates to its bytes
+    (@check $lit:literal) => {{
+        const BYTES: &[u8] = $crate::__private::Literal($lit).bytes();
+        const {
+            assert!(
+                !$crate::__private::contains_nul(BYTES),
+                "literal contains an interior null byte"
+            );
+        }
+        BYTES
+    }};
+
+    () => {
+        $crate::CStringArray::empty()
+    };
+    ($($items:tt)+) => {
+        $crate::cstring_array!(@lit [] $($items)+)
+    };
+}
+
+/// Support code for [`cstring_array!`](crate::cstring_array). Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use alloc::ffi::{CString, NulError};
+    use core::ffi::CStr;
+
+    use crate::{array::CStringArray, error::CStringArrayError};
+
+    /// A literal accepted by the macro, exposing its bytes in const context.
+    pub struct Literal<T>(pub T);
+
+    impl<'a> Literal<&'a str> {
+        /// Returns the bytes of a string literal.
+        pub const fn bytes(self) -> &'a [u8] {
+            self.0.as_bytes()
+        }
+    }
+
+    impl<'a> Literal<&'a CStr> {
+        /// Returns the bytes of a C string literal, without the terminator.
+        pub const fn bytes(self) -> &'a [u8] {
+            self.0.to_bytes()
+        }
+    }
+
+    impl<'a, const N: usize> Literal<&'a [u8; N]> {
+        /// Returns the bytes of a byte string literal.
+        pub const fn bytes(self) -> &'a [u8] {
+            self.0
+        }
+    }
+
+    /// Returns `true` if `bytes` contains a null byte.
+    pub const fn contains_nul(bytes: &[u8]) -> bool {
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == 0 {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Builds an array from literals already checked at compile time.
+    pub fn from_literals(literals: &[&[u8]]) -> CStringArray {
+        CStringArray::from_cstrings_allow_empty(
+            literals
+                .iter()
+                .map(|s| CString::new(*s).expect("literal checked at compile time"))
+                .collect()
+        )
+    }
+
+    /// Builds an array from converted elements, reporting the index of the
+    /// first failure.
+    pub fn from_items<const N: usize>(
+        items: [Result<CString, NulError>; N]
+    ) -> Result<CStringArray, CStringArrayError> {
+        let mut array = CStringArray::with_capacity(N);
+        for (i, item) in items.into_iter().enumerate() {
+            array.push(item.map_err(|e| CStringArrayError::nul_at(i, e))?);
+        }
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use crate::{CStringArray, CStringArrayError};
+
+    #[test]
+    fn test_literals_only() {
+        let array: CStringArray = cstring_array!["a", "b", "c"];
+        assert_eq!(array, CStringArray::try_from(["a", "b", "c"]).unwrap());
+
+        let trailing: CStringArray = cstring_array!["x",];
+        assert_eq!(trailing.len(), 1);
+    }
+
+    #[test]
+    fn test_c_string_and_byte_string_literals() {
+        let array: CStringArray = cstring_array![c"c", b"b", "s", b"\xff"];
+        assert_eq!(array[0], *c"c");
+        assert_eq!(array[1], *c"b");
+        assert_eq!(array[2], *c"s");
+        assert_eq!(array[3].as_bytes(), b"\xff");
+
+        let name = String::from("name");
+        let array = cstring_array![c"c", name, b"b"].unwrap();
+        assert_eq!(array, CStringArray::try_from(["c", "name", "b"]).unwrap());
+    }
+
+    #[test]
+    fn test_empty() {
+        let array: CStringArray = cstring_array![];
+        assert!(array.is_empty());
+        assert!(unsafe { (*array.as_ptr()).is_null() });
+    }
+
+    #[test]
+    fn test_mixed_elements() {
+        let name = "world";
+        let owned = String::from("owned");
+        let result: Result<CStringArray, CStringArrayError> = cstring_array![
+            "lit",
+            format!("hello {}", name),
+            owned,
+            "middle",
+            CString::new("c").unwrap(),
+            b"bytes".to_vec(),
+            name,
+        ];
+
+        let array = result.unwrap();
+        let values: Vec<&str> = array.iter().map(|s| s.to_str().unwrap()).collect();
+        assert_eq!(
+            values,
+            [
+                "lit",
+                "hello world",
+                "owned",
+                "middle",
+                "c",
+                "bytes",
+                "world"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literal_method_call_is_runtime() {
+        let array = cstring_array!["a".to_uppercase(), "b"].unwrap();
+        assert_eq!(array[0].to_str().unwrap(), "A");
+    }
+
+    #[test]
+    fn test_runtime_error_index() {
+        let bad = String::from("b\0d");
+        let err = cstring_array!["ok", "fine", bad].unwrap_err();
+        assert_eq!(err.index(), Some(2));
+    }
+}
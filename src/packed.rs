@@ -0,0 +1,473 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Single-allocation string array with contiguous storage.
+//!
+//! [`PackedCStringArray`] stores every string, including its null terminator,
+//! back to back in one byte buffer and points into it, the same way the kernel
+//! lays out `argv` for a new process. Construction needs exactly two
+//! allocations regardless of the number of strings.
+
+use alloc::{boxed::Box, ffi::CString, vec::Vec};
+use core::{
+    ffi::{CStr, c_char},
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    ops::Index,
+    ptr::null
+};
+
+use crate::{
+    array::CStringArray,
+    error::{CStringArrayError, CStringArrayError::EmptyArray}
+};
+
+/// String array packed into a single contiguous buffer.
+///
+/// Exposes the same read-only API as [`CStringArray`] (`as_ptr`, `len`,
+/// `get`, `iter`), but stores the bytes of all strings in one allocation
+/// instead of one allocation per string. This improves locality for large
+/// arrays such as a full environment or a compiler file list, and makes
+/// [`Clone`] a single buffer copy.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::PackedCStringArray;
+///
+/// let array = PackedCStringArray::new(&["cc", "-c", "main.c"]).unwrap();
+///
+/// let ptr: *const *const c_char = array.as_ptr();
+/// assert_eq!(array.len(), 3);
+/// assert_eq!(array.as_bytes(), b"cc\0-c\0main.c\0");
+/// ```
+#[derive(Debug)]
+pub struct PackedCStringArray {
+    buffer:   Box<[u8]>,
+    pointers: Vec<*const c_char>
+}
+
+impl PackedCStringArray {
+    /// Creates a packed array by copying the given strings into one buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `strings` - Slice of strings or byte strings without null terminators
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if any string contains an interior
+    /// null byte. Returns `CStringArrayError::EmptyArray` if the input slice
+    /// is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::PackedCStringArray;
+    ///
+    /// let args = vec!["foo".to_string(), "bar".to_string()];
+    /// let array = PackedCStringArray::new(&args).unwrap();
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    pub fn new<S: AsRef<[u8]>>(strings: &[S]) -> Result<Self, CStringArrayError> {
+        if strings.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        let mut total = 0;
+        for (i, s) in strings.iter().enumerate() {
+            let bytes = s.as_ref();
+            if bytes.contains(&0) {
+                let error = CString::new(bytes).expect_err("string contains a null byte");
+                return Err(CStringArrayError::nul_at(i, error));
+            }
+            total += bytes.len() + 1;
+        }
+
+        let mut buffer = Vec::with_capacity(total);
+        for s in strings {
+            buffer.extend_from_slice(s.as_ref());
+            buffer.push(0);
+        }
+
+        Ok(Self::from_buffer(
+            buffer.into_boxed_slice(),
+            strings.iter().map(|s| s.as_ref().len() + 1),
+            strings.len()
+        ))
+    }
+
+    /// Creates a packed array from C strings, which are already known to be
+    /// free of interior null bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::EmptyArray` if the input slice is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::PackedCStringArray;
+    ///
+    /// let array = PackedCStringArray::from_cstrs(&[c"hello", c"world"]).unwrap();
+    /// assert_eq!(array.get(1).unwrap(), c"world");
+    /// ```
+    pub fn from_cstrs<S: AsRef<CStr>>(strings: &[S]) -> Result<Self, CStringArrayError> {
+        if strings.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        let total = strings.iter().map(|s| s.as_ref().count_bytes() + 1).sum();
+        let mut buffer = Vec::with_capacity(total);
+        for s in strings {
+            buffer.extend_from_slice(s.as_ref().to_bytes_with_nul());
+        }
+
+        Ok(Self::from_buffer(
+            buffer.into_boxed_slice(),
+            strings.iter().map(|s| s.as_ref().count_bytes() + 1),
+            strings.len()
+        ))
+    }
+
+    /// Builds the pointer table for `buffer` from the length of every
+    /// string including its null terminator.
+    fn from_buffer<I>(buffer: Box<[u8]>, lengths: I, count: usize) -> Self
+    where
+        I: IntoIterator<Item = usize>
+    {
+        let base = buffer.as_ptr();
+        let mut pointers: Vec<*const c_char> = Vec::with_capacity(count + 1);
+        let mut offset = 0;
+        for len in lengths {
+            pointers.push(base.wrapping_add(offset).cast());
+            offset += len;
+        }
+        pointers.push(null());
+
+        Self {
+            buffer,
+            pointers
+        }
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `char**`.
+    ///
+    /// The returned pointer is valid for the lifetime of this
+    /// `PackedCStringArray`. The pointer array is null-terminated as required
+    /// by C conventions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::{CStr, c_char};
+    ///
+    /// use cstring_array::PackedCStringArray;
+    ///
+    /// let array = PackedCStringArray::new(&["test"]).unwrap();
+    /// let ptr: *const *const c_char = array.as_ptr();
+    /// assert_eq!(unsafe { CStr::from_ptr(*ptr) }, c"test");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.pointers.as_ptr()
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pointers.len() - 1
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string at the specified index, or `None` if the index is
+    /// out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::PackedCStringArray;
+    ///
+    /// let array = PackedCStringArray::new(&["first", "second"]).unwrap();
+    /// assert_eq!(array.get(0).unwrap(), c"first");
+    /// assert!(array.get(2).is_none());
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&CStr> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let start = self.offset(index);
+        let end = if index + 1 < self.len() {
+            self.offset(index + 1)
+        } else {
+            self.buffer.len()
+        };
+
+        // SAFETY: every string was written as bytes without interior nulls
+        // followed by exactly one null terminator, and `start..end` covers one
+        // such string.
+        Some(unsafe { CStr::from_bytes_with_nul_unchecked(&self.buffer[start..end]) })
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::PackedCStringArray;
+    ///
+    /// let array = PackedCStringArray::new(&["a", "b"]).unwrap();
+    /// let strings: Vec<_> = array.iter().collect();
+    /// assert_eq!(strings, [c"a", c"b"]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> PackedIter<'_> {
+        PackedIter {
+            array: self,
+            front: 0,
+            back:  self.len()
+        }
+    }
+
+    /// Returns the packed buffer, with every string followed by its null
+    /// terminator.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Byte offset of the string at `index` inside the buffer.
+    fn offset(&self, index: usize) -> usize {
+        self.pointers[index] as usize - self.buffer.as_ptr() as usize
+    }
+}
+
+unsafe impl Send for PackedCStringArray {}
+unsafe impl Sync for PackedCStringArray {}
+
+// ============================================================================
+// Iterator
+// ============================================================================
+
+/// Iterator over the strings of a [`PackedCStringArray`].
+///
+/// Created by [`PackedCStringArray::iter`].
+#[derive(Debug, Clone)]
+pub struct PackedIter<'a> {
+    array: &'a PackedCStringArray,
+    front: usize,
+    back:  usize
+}
+
+impl<'a> Iterator for PackedIter<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let item = self.array.get(self.front);
+        self.front += 1;
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for PackedIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.array.get(self.back)
+    }
+}
+
+impl ExactSizeIterator for PackedIter<'_> {}
+
+impl FusedIterator for PackedIter<'_> {}
+
+impl<'a> IntoIterator for &'a PackedCStringArray {
+    type Item = &'a CStr;
+    type IntoIter = PackedIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ============================================================================
+// Standard Traits
+// ============================================================================
+
+impl Clone for PackedCStringArray {
+    fn clone(&self) -> Self {
+        let buffer = self.buffer.clone();
+        let base = buffer.as_ptr();
+        let mut pointers: Vec<*const c_char> = Vec::with_capacity(self.pointers.len());
+        pointers.extend((0..self.len()).map(|i| base.wrapping_add(self.offset(i)).cast()));
+        pointers.push(null());
+
+        Self {
+            buffer,
+            pointers
+        }
+    }
+}
+
+impl PartialEq for PackedCStringArray {
+    fn eq(&self, other: &Self) -> bool {
+        self.buffer == other.buffer
+    }
+}
+
+impl Eq for PackedCStringArray {}
+
+impl Hash for PackedCStringArray {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.buffer.hash(state);
+    }
+}
+
+impl Index<usize> for PackedCStringArray {
+    type Output = CStr;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(s) => s,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            )
+        }
+    }
+}
+
+impl From<&CStringArray> for PackedCStringArray {
+    /// Packs the strings of a [`CStringArray`] into a single buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, PackedCStringArray};
+    ///
+    /// let array = CStringArray::try_from(["a", "b"]).unwrap();
+    /// let packed = PackedCStringArray::from(&array);
+    /// assert_eq!(packed.as_bytes(), b"a\0b\0");
+    /// ```
+    fn from(array: &CStringArray) -> Self {
+        let total = array.iter().map(|s| s.as_bytes_with_nul().len()).sum();
+        let mut buffer = Vec::with_capacity(total);
+        for s in array {
+            buffer.extend_from_slice(s.as_bytes_with_nul());
+        }
+
+        Self::from_buffer(
+            buffer.into_boxed_slice(),
+            array.iter().map(|s| s.as_bytes_with_nul().len()),
+            array.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_pointer_table(array: &PackedCStringArray, expected: &[&str]) {
+        assert_eq!(array.len(), expected.len());
+
+        let ptr = array.as_ptr();
+        unsafe {
+            for (i, value) in expected.iter().enumerate() {
+                assert_eq!(CStr::from_ptr(*ptr.add(i)).to_str().unwrap(), *value);
+            }
+            assert!((*ptr.add(expected.len())).is_null());
+        }
+    }
+
+    #[test]
+    fn test_new_packs_strings() {
+        let array = PackedCStringArray::new(&["prog", "", "arg"]).unwrap();
+        assert_eq!(array.as_bytes(), b"prog\0\0arg\0");
+        assert_pointer_table(&array, &["prog", "", "arg"]);
+    }
+
+    #[test]
+    fn test_new_rejects_empty_and_nul() {
+        let empty: [&str; 0] = [];
+        assert!(matches!(PackedCStringArray::new(&empty), Err(EmptyArray)));
+
+        match PackedCStringArray::new(&["ok", "b\0ad"]) {
+            Err(CStringArrayError::NulError(e)) => {
+                assert_eq!(e.index(), Some(1));
+                assert_eq!(e.nul_position(), 1);
+            }
+            _ => panic!("Expected NulError")
+        }
+    }
+
+    #[test]
+    fn test_get_and_iter() {
+        let array = PackedCStringArray::new(&["a", "bb", "ccc"]).unwrap();
+        assert_eq!(array.get(0).unwrap(), c"a");
+        assert_eq!(array.get(2).unwrap(), c"ccc");
+        assert!(array.get(3).is_none());
+        assert_eq!(&array[1], c"bb");
+
+        let forward: Vec<&CStr> = array.iter().collect();
+        assert_eq!(forward, [c"a", c"bb", c"ccc"]);
+
+        let backward: Vec<&CStr> = array.iter().rev().collect();
+        assert_eq!(backward, [c"ccc", c"bb", c"a"]);
+        assert_eq!(array.iter().len(), 3);
+    }
+
+    #[test]
+    fn test_clone_points_into_own_buffer() {
+        let array = PackedCStringArray::new(&["x", "yz"]).unwrap();
+        let cloned = array.clone();
+
+        assert_eq!(array, cloned);
+        assert_ne!(array.as_ptr(), cloned.as_ptr());
+        unsafe {
+            assert_eq!(*cloned.as_ptr(), cloned.as_bytes().as_ptr().cast());
+        }
+        drop(array);
+        assert_pointer_table(&cloned, &["x", "yz"]);
+    }
+
+    #[test]
+    fn test_from_cstrs_and_cstring_array() {
+        let from_cstrs = PackedCStringArray::from_cstrs(&[c"a", c"b"]).unwrap();
+        let array = CStringArray::try_from(["a", "b"]).unwrap();
+        let from_array = PackedCStringArray::from(&array);
+
+        assert_eq!(from_cstrs, from_array);
+        assert_pointer_table(&from_array, &["a", "b"]);
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Handing ownership of a `char**` over to C code.
+//!
+//! [`CStringArray::as_ptr`] only lends pointers that die with the Rust value.
+//! The methods in this module give the memory away instead: [`leak`] for
+//! libraries that keep `argv` for the life of the process, and, with the
+//! `libc` feature, `into_raw_malloc` and `from_raw_malloc` for libraries
+//! that release it with `free()`.
+//!
+//! [`leak`]: CStringArray::leak
+
+use alloc::{ffi::CString, vec::Vec};
+#[cfg(feature = "libc")]
+use core::alloc::Layout;
+use core::{ffi::c_char, ptr::null_mut};
+
+use crate::array::CStringArray;
+#[cfg(feature = "libc")]
+use crate::foreign::{Deallocator, ForeignCStringArray};
+
+impl CStringArray {
+    /// Consumes the array and leaks its memory, returning a `'static` pointer
+    /// table.
+    ///
+    /// The returned slice includes the trailing null pointer, so its length
+    /// is `len() + 1` and `as_mut_ptr()` on it yields a `char**` that stays
+    /// valid for the rest of the program. Useful for `gtk_init`-style APIs
+    /// that keep, and may reorder, `argv`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::{CStr, c_char};
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let argv: &'static mut [*mut c_char] =
+    ///     CStringArray::try_from(["app", "--debug"]).unwrap().leak();
+    /// assert_eq!(argv.len(), 3);
+    /// assert!(argv[2].is_null());
+    /// assert_eq!(unsafe { CStr::from_ptr(argv[1]) }, c"--debug");
+    /// ```
+    #[must_use]
+    pub fn leak(self) -> &'static mut [*mut c_char] {
+        let mut pointers: Vec<*mut c_char> = self
+            .into_strings()
+            .into_iter()
+            .map(CString::into_raw)
+            .collect();
+        pointers.push(null_mut());
+        pointers.leak()
+    }
+
+    /// Consumes the array and copies it into `malloc` allocations that C
+    /// code can release with `free()`.
+    ///
+    /// Every string gets its own allocation, and the null-terminated pointer
+    /// table gets one more, which is the layout C code expects when it frees
+    /// each element and then the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let raw = CStringArray::try_from(["a", "b"])
+    ///     .unwrap()
+    ///     .into_raw_malloc();
+    ///
+    /// // ... hand `raw` to a C library that frees it ...
+    /// # let _ = unsafe { CStringArray::from_raw_malloc(raw) };
+    /// ```
+    #[cfg(feature = "libc")]
+    #[must_use]
+    pub fn into_raw_malloc(self) -> *mut *mut c_char {
+        let len = self.len();
+        let table = malloc(Layout::array::<*mut c_char>(len + 1).expect("capacity overflow"))
+            .cast::<*mut c_char>();
+
+        for (i, s) in self.iter().enumerate() {
+            let bytes = s.to_bytes_with_nul();
+            let copy = malloc(Layout::for_value(bytes)).cast::<u8>();
+            // SAFETY: `copy` holds `bytes.len()` bytes and `table` holds
+            // `len + 1` pointers.
+            unsafe {
+                copy.copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+                *table.add(i) = copy.cast();
+            }
+        }
+        // SAFETY: the last slot of `table`.
+        unsafe { *table.add(len) = null_mut() };
+        table
+    }
+
+    /// Takes back an array produced by [`into_raw_malloc`], copying its
+    /// strings and freeing every element and the array with `free()`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a non-null, null-terminated array of null-terminated
+    /// strings, where the array and every string were allocated with
+    /// `malloc` and are not used or freed elsewhere afterwards.
+    ///
+    /// [`into_raw_malloc`]: Self::into_raw_malloc
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let original = CStringArray::try_from(["x", "y"]).unwrap();
+    /// let raw = original.clone().into_raw_malloc();
+    ///
+    /// let restored = unsafe { CStringArray::from_raw_malloc(raw) };
+    /// assert_eq!(restored, original);
+    /// ```
+    #[cfg(feature = "libc")]
+    #[must_use]
+    pub unsafe fn from_raw_malloc(ptr: *mut *mut c_char) -> Self {
+        // SAFETY: guaranteed by the caller.
+        let foreign =
+            unsafe { ForeignCStringArray::from_raw(ptr, Deallocator::Elements(libc::free)) }
+                .expect("pointer must not be null");
+
+        Self::from_cstrings_allow_empty(foreign.iter().map(CString::from).collect())
+    }
+}
+
+/// Allocates `layout.size()` bytes with `malloc`, aborting on failure like
+/// the global allocator does.
+#[cfg(feature = "libc")]
+fn malloc(layout: Layout) -> *mut libc::c_void {
+    // SAFETY: `malloc` can be called with any size.
+    let ptr = unsafe { libc::malloc(layout.size()) };
+    if ptr.is_null() {
+        alloc::alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ffi::CStr;
+
+    use super::*;
+
+    #[test]
+    fn test_leak_layout() {
+        let argv = CStringArray::try_from(["prog", ""]).unwrap().leak();
+        assert_eq!(argv.len(), 3);
+        unsafe {
+            assert_eq!(CStr::from_ptr(argv[0]), c"prog");
+            assert_eq!(CStr::from_ptr(argv[1]), c"");
+        }
+        assert!(argv[2].is_null());
+    }
+
+    #[test]
+    fn test_leak_empty() {
+        let argv = CStringArray::empty().leak();
+        assert_eq!(argv.len(), 1);
+        assert!(argv[0].is_null());
+    }
+
+    #[cfg(feature = "libc")]
+    #[test]
+    fn test_malloc_round_trip() {
+        let original = CStringArray::try_from(["ls", "-l", "", "/tmp"]).unwrap();
+        let raw = original.clone().into_raw_malloc();
+        unsafe {
+            assert_eq!(CStr::from_ptr(*raw.add(1)), c"-l");
+            assert!((*raw.add(4)).is_null());
+        }
+
+        let restored = unsafe { CStringArray::from_raw_malloc(raw) };
+        assert_eq!(restored, original);
+    }
+
+    #[cfg(feature = "libc")]
+    #[test]
+    fn test_malloc_empty() {
+        let raw = CStringArray::empty().into_raw_malloc();
+        assert!(unsafe { (*raw).is_null() });
+        assert!(unsafe { CStringArray::from_raw_malloc(raw) }.is_empty());
+    }
+}
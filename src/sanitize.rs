@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Repair policies for interior null bytes.
+//!
+//! This module provides [`NulPolicy`], used by
+//! [`CStringArray::new_lossy`](crate::CStringArray::new_lossy) to repair
+//! untrusted input instead of rejecting it.
+
+use alloc::vec::Vec;
+use core::num::NonZeroU8;
+
+/// How interior null bytes are handled by lossy constructors.
+///
+/// # Example
+///
+/// ```
+/// use std::num::NonZeroU8;
+///
+/// use cstring_array::{CStringArray, NulPolicy};
+///
+/// let labels = vec!["user\0name".to_string()];
+///
+/// let (array, _) = CStringArray::new_lossy(labels.clone(), NulPolicy::Truncate).unwrap();
+/// assert_eq!(array[0].to_str().unwrap(), "user");
+///
+/// let (array, _) = CStringArray::new_lossy(labels.clone(), NulPolicy::Strip).unwrap();
+/// assert_eq!(array[0].to_str().unwrap(), "username");
+///
+/// let question = NonZeroU8::new(b'?').unwrap();
+/// let (array, _) = CStringArray::new_lossy(labels, NulPolicy::Replace(question)).unwrap();
+/// assert_eq!(array[0].to_str().unwrap(), "user?name");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NulPolicy {
+    /// Cut the value at the first null byte, which is what C code would see
+    Truncate,
+    /// Remove every null byte
+    Strip,
+    /// Replace every null byte with the given non-zero byte
+    Replace(NonZeroU8)
+}
+
+impl NulPolicy {
+    /// Applies the policy to `bytes` in place and returns `true` if they were
+    /// changed.
+    pub(crate) fn apply(self, bytes: &mut Vec<u8>) -> bool {
+        let Some(first) = bytes.iter().position(|&b| b == 0) else {
+            return false;
+        };
+
+        match self {
+            Self::Truncate => bytes.truncate(first),
+            Self::Strip => bytes.retain(|&b| b != 0),
+            Self::Replace(byte) => {
+                for b in &mut bytes[first..] {
+                    if *b == 0 {
+                        *b = byte.get();
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_policies() {
+        let mut bytes = b"a\0b\0c".to_vec();
+        assert!(NulPolicy::Truncate.apply(&mut bytes));
+        assert_eq!(bytes, b"a");
+
+        let mut bytes = b"a\0b\0c".to_vec();
+        assert!(NulPolicy::Strip.apply(&mut bytes));
+        assert_eq!(bytes, b"abc");
+
+        let mut bytes = b"\0b\0".to_vec();
+        assert!(NulPolicy::Replace(NonZeroU8::new(b'_').unwrap()).apply(&mut bytes));
+        assert_eq!(bytes, b"_b_");
+    }
+
+    #[test]
+    fn test_apply_leaves_clean_input() {
+        let mut bytes = b"clean".to_vec();
+        assert!(!NulPolicy::Strip.apply(&mut bytes));
+        assert_eq!(bytes, b"clean");
+    }
+}
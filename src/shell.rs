@@ -0,0 +1,367 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Splitting command lines into words and quoting them back, following the
+//! POSIX shell quoting rules.
+//!
+//! Only quoting is handled: parameters, commands and globs are not expanded,
+//! and operators such as `|` or `;` are ordinary characters.
+
+use alloc::{ffi::CString, string::String, vec::Vec};
+
+use crate::{
+    array::CStringArray,
+    error::{CStringArrayError, ShellErrorKind}
+};
+
+impl CStringArray {
+    /// Splits a command line into words, like a POSIX shell would.
+    ///
+    /// Words are separated by spaces, tabs and newlines. Single quotes
+    /// preserve everything up to the closing quote. Double quotes preserve
+    /// everything except a backslash followed by `$`, `` ` ``, `"`, `\` or a
+    /// newline. Outside quotes, a backslash preserves the next character. A
+    /// backslash followed by a newline is removed, and a `#` at the start of
+    /// a word begins a comment running to the end of the line.
+    ///
+    /// Blank input gives an empty array.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::ShellSyntax` with the byte position of the
+    /// problem for an unterminated quote, a trailing backslash, or a null
+    /// byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, CStringArrayError};
+    ///
+    /// let argv = CStringArray::from_shell_words(r#"grep -e 'a b' "c\"d" e\ f"#).unwrap();
+    /// assert_eq!(
+    ///     argv,
+    ///     CStringArray::try_from(["grep", "-e", "a b", "c\"d", "e f"]).unwrap()
+    /// );
+    ///
+    /// let err = CStringArray::from_shell_words("echo 'oops").unwrap_err();
+    /// assert!(matches!(
+    ///     err,
+    ///     CStringArrayError::ShellSyntax {
+    ///         position: 5,
+    ///         ..
+    ///     }
+    /// ));
+    /// ```
+    pub fn from_shell_words(line: &str) -> Result<Self, CStringArrayError> {
+        Self::from_shell_bytes(line.as_bytes())
+    }
+
+    /// Splits a command line given as bytes into words, like
+    /// [`from_shell_words`](Self::from_shell_words).
+    ///
+    /// The line does not need to be valid UTF-8, which allows reading back
+    /// the output of [`to_shell_bytes`](Self::to_shell_bytes) for any array.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::ShellSyntax` with the byte position of the
+    /// problem for an unterminated quote, a trailing backslash, or a null
+    /// byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let argv = CStringArray::from_shell_bytes(b"cat '\xffname'").unwrap();
+    /// assert_eq!(argv[1].to_bytes(), b"\xffname");
+    /// ```
+    pub fn from_shell_bytes(bytes: &[u8]) -> Result<Self, CStringArrayError> {
+        let error = |position, kind| CStringArrayError::ShellSyntax {
+            position,
+            kind
+        };
+
+        let mut words = Vec::new();
+        let mut word: Option<Vec<u8>> = None;
+        let mut i = 0;
+        while let Some(&byte) = bytes.get(i) {
+            match byte {
+                0 => return Err(error(i, ShellErrorKind::NulByte)),
+                b' ' | b'\t' | b'\n' => {
+                    if let Some(word) = word.take() {
+                        words.push(word);
+                    }
+                }
+                b'#' if word.is_none() => {
+                    while bytes.get(i + 1).is_some_and(|&b| b != b'\n') {
+                        i += 1;
+                    }
+                }
+                b'\\' => match bytes.get(i + 1) {
+                    None => return Err(error(i, ShellErrorKind::TrailingBackslash)),
+                    Some(b'\n') => i += 1,
+                    Some(0) => return Err(error(i + 1, ShellErrorKind::NulByte)),
+                    Some(&next) => {
+                        word.get_or_insert_default().push(next);
+                        i += 1;
+                    }
+                },
+                b'\'' => {
+                    let len = bytes[i + 1..]
+                        .iter()
+                        .position(|&b| b == b'\'')
+                        .ok_or(error(i, ShellErrorKind::UnterminatedSingleQuote))?;
+                    let quoted = &bytes[i + 1..i + 1 + len];
+                    if let Some(nul) = quoted.iter().position(|&b| b == 0) {
+                        return Err(error(i + 1 + nul, ShellErrorKind::NulByte));
+                    }
+                    word.get_or_insert_default().extend_from_slice(quoted);
+                    i += len + 1;
+                }
+                b'"' => {
+                    let start = i;
+                    let word = word.get_or_insert_default();
+                    loop {
+                        i += 1;
+                        match bytes.get(i) {
+                            None => {
+                                return Err(error(start, ShellErrorKind::UnterminatedDoubleQuote));
+                            }
+                            Some(b'"') => break,
+                            Some(0) => return Err(error(i, ShellErrorKind::NulByte)),
+                            Some(b'\\') => match bytes.get(i + 1) {
+                                Some(b'\n') => i += 1,
+                                Some(&next @ (b'$' | b'`' | b'"' | b'\\')) => {
+                                    word.push(next);
+                                    i += 1;
+                                }
+                                _ => word.push(b'\\')
+                            },
+                            Some(&other) => word.push(other)
+                        }
+                    }
+                }
+                other => word.get_or_insert_default().push(other)
+            }
+            i += 1;
+        }
+        words.extend(word);
+
+        let strings = words
+            .into_iter()
+            .map(|word| CString::new(word).expect("null bytes are rejected while parsing"))
+            .collect();
+        Ok(Self::from_cstrings_allow_empty(strings))
+    }
+
+    /// Joins the array into a command line that a POSIX shell, or
+    /// [`from_shell_words`](Self::from_shell_words), splits back into the
+    /// same words.
+    ///
+    /// Elements made only of characters that are never special to the shell
+    /// are left as is; the others are put in single quotes.
+    ///
+    /// Returns `None` if an element is not valid UTF-8. Use
+    /// [`to_shell_bytes`](Self::to_shell_bytes) to quote such arrays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::CStringArray;
+    ///
+    /// let argv = CStringArray::try_from(["echo", "it's", "", "a b", "--x=1"]).unwrap();
+    /// let line = argv.to_shell_string().unwrap();
+    /// assert_eq!(line, r"echo 'it'\''s' '' 'a b' --x=1");
+    ///
+    /// assert_eq!(CStringArray::from_shell_words(&line).unwrap(), argv);
+    /// ```
+    #[must_use]
+    pub fn to_shell_string(&self) -> Option<String> {
+        String::from_utf8(self.to_shell_bytes()).ok()
+    }
+
+    /// Joins the array into a command line, like
+    /// [`to_shell_string`](Self::to_shell_string), without requiring the
+    /// elements to be valid UTF-8.
+    ///
+    /// Every byte is kept as is, so
+    /// [`from_shell_bytes`](Self::from_shell_bytes) gives back the same array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let argv = CStringArray::from_cstrings(vec![
+    ///     CString::from(c"ls"),
+    ///     CString::new(b"caf\xe9 menu".to_vec()).unwrap(),
+    /// ])
+    /// .unwrap();
+    /// let line = argv.to_shell_bytes();
+    /// assert_eq!(line, b"ls 'caf\xe9 menu'");
+    ///
+    /// assert_eq!(CStringArray::from_shell_bytes(&line).unwrap(), argv);
+    /// ```
+    #[must_use]
+    pub fn to_shell_bytes(&self) -> Vec<u8> {
+        let mut line = Vec::new();
+        for (i, s) in self.iter().enumerate() {
+            if i > 0 {
+                line.push(b' ');
+            }
+            let word = s.to_bytes();
+            if needs_quotes(word, i == 0) {
+                line.push(b'\'');
+                for &byte in word {
+                    if byte == b'\'' {
+                        line.extend_from_slice(br"'\''");
+                    } else {
+                        line.push(byte);
+                    }
+                }
+                line.push(b'\'');
+            } else {
+                line.extend_from_slice(word);
+            }
+        }
+        line
+    }
+}
+
+/// Returns whether `word` must be quoted to be read back as a single word.
+///
+/// An `=` is only special in the first word, where it would make the shell
+/// read a variable assignment.
+fn needs_quotes(word: &[u8], first: bool) -> bool {
+    word.is_empty()
+        || !word.iter().all(|&b| {
+            b.is_ascii_alphanumeric()
+                || matches!(
+                    b,
+                    b'%' | b'+' | b',' | b'-' | b'.' | b'/' | b':' | b'@' | b'_'
+                )
+                || (b == b'=' && !first)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        CStringArray::from_shell_words(line)
+            .unwrap()
+            .iter()
+            .map(|s| s.to_str().unwrap().into())
+            .collect()
+    }
+
+    fn error(line: &str) -> (usize, ShellErrorKind) {
+        match CStringArray::from_shell_words(line) {
+            Err(CStringArrayError::ShellSyntax {
+                position,
+                kind
+            }) => (position, kind),
+            other => panic!("Expected ShellSyntax, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn test_blank_input() {
+        assert!(words("").is_empty());
+        assert!(words(" \t\n ").is_empty());
+        assert!(words("# only a comment").is_empty());
+    }
+
+    #[test]
+    fn test_quoting_rules() {
+        assert_eq!(words("  a   b\tc\nd "), ["a", "b", "c", "d"]);
+        assert_eq!(words(r#"'a "b" \c'"#), [r#"a "b" \c"#]);
+        assert_eq!(words(r#""a 'b' \c \$ \` \" \\""#), [r#"a 'b' \c $ ` " \"#]);
+        assert_eq!(words(r"a\ b \'c\' \\"), ["a b", "'c'", "\\"]);
+        assert_eq!(words("'' \"\" a''b"), ["", "", "ab"]);
+        assert_eq!(words("x'y'\"z\"w"), ["xyzw"]);
+        assert_eq!(
+            words("$HOME `pwd` *.rs a|b;c"),
+            ["$HOME", "`pwd`", "*.rs", "a|b;c"]
+        );
+        assert_eq!(words("ünï 'cödé'"), ["ünï", "cödé"]);
+    }
+
+    #[test]
+    fn test_line_continuation_and_comments() {
+        assert_eq!(words("a\\\nb \"c\\\nd\" 'e\\\nf'"), ["ab", "cd", "e\\\nf"]);
+        assert_eq!(words("a #comment\nb a#b '#'"), ["a", "b", "a#b", "#"]);
+    }
+
+    #[test]
+    fn test_error_positions() {
+        assert_eq!(
+            error("ok 'open"),
+            (3, ShellErrorKind::UnterminatedSingleQuote)
+        );
+        assert_eq!(
+            error("ok \"open\\\""),
+            (3, ShellErrorKind::UnterminatedDoubleQuote)
+        );
+        assert_eq!(error("ok \\"), (3, ShellErrorKind::TrailingBackslash));
+        assert_eq!(error("a\0"), (1, ShellErrorKind::NulByte));
+        assert_eq!(error("'ab\0'"), (3, ShellErrorKind::NulByte));
+        assert_eq!(error("\"ab\0\""), (3, ShellErrorKind::NulByte));
+        assert_eq!(error("\\\0"), (1, ShellErrorKind::NulByte));
+    }
+
+    #[test]
+    fn test_to_shell_string() {
+        let argv =
+            CStringArray::try_from(["ls", "-l", "/tmp/a b", "x=1", "$y", "~", "#"]).unwrap();
+        assert_eq!(
+            argv.to_shell_string().unwrap(),
+            "ls -l '/tmp/a b' x=1 '$y' '~' '#'"
+        );
+
+        let assignment = CStringArray::try_from(["A=1", "prog"]).unwrap();
+        assert_eq!(assignment.to_shell_string().unwrap(), "'A=1' prog");
+
+        assert_eq!(CStringArray::empty().to_shell_string().unwrap(), "");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let argv = CStringArray::try_from([
+            "sh",
+            "-c",
+            "echo \"$1\" 'x'",
+            "",
+            "'",
+            "\"",
+            "\\",
+            "a\nb",
+            "\t",
+            "#x",
+            "ü"
+        ])
+        .unwrap();
+        let line = argv.to_shell_string().unwrap();
+        assert_eq!(CStringArray::from_shell_words(&line).unwrap(), argv);
+    }
+
+    #[test]
+    fn test_non_utf8_round_trip() {
+        let argv = CStringArray::from_cstrings(vec![
+            CString::new(b"\xff".to_vec()).unwrap(),
+            CString::new(b"it\x80's \xc3".to_vec()).unwrap(),
+            CString::new(b"\xfe\xfd".to_vec()).unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(argv.to_shell_string(), None);
+
+        let line = argv.to_shell_bytes();
+        assert_eq!(line, b"'\xff' 'it\x80'\\''s \xc3' '\xfe\xfd'");
+        assert_eq!(CStringArray::from_shell_bytes(&line).unwrap(), argv);
+    }
+}
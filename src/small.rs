@@ -0,0 +1,421 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! String array with an inline pointer table for short lists.
+//!
+//! Most command lines have only a handful of arguments. [`SmallCStringArray`]
+//! keeps up to `N` pointers plus the null terminator inside the value itself
+//! and moves the table to the heap only when more than `N` strings are stored.
+
+use alloc::{ffi::CString, string::String, vec::Vec};
+use core::{ffi::c_char, ptr::null, slice::Iter};
+
+use crate::{
+    array::CStringArray,
+    error::{CStringArrayError, CStringArrayError::EmptyArray},
+    inline::PointerTable
+};
+
+/// Storage of the null-terminated pointer table.
+#[derive(Debug, Clone)]
+enum Pointers<const N: usize> {
+    /// Up to `N` pointers stored inline
+    Inline(PointerTable<N>),
+    /// Table moved to the heap after growing past `N` strings
+    Heap(Vec<*const c_char>)
+}
+
+/// String array whose pointer table lives inline for up to `N` strings.
+///
+/// Behaves like [`CStringArray`], but avoids the separate heap allocation of
+/// the pointer table while it holds at most `N` strings. Pushing more strings
+/// spills the table to the heap transparently.
+///
+/// Because the inline table is part of the value, the pointer returned by
+/// [`as_ptr`](Self::as_ptr) is invalidated when the array is moved, not only
+/// when it is dropped or modified. Take the pointer right before the FFI
+/// call.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::SmallCStringArray;
+///
+/// let array = SmallCStringArray::<4>::new(vec!["ls".to_string(), "-l".to_string()]).unwrap();
+/// assert!(array.is_inline());
+///
+/// let ptr: *const *const c_char = array.as_ptr();
+/// assert_eq!(array.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct SmallCStringArray<const N: usize> {
+    strings:  Vec<CString>,
+    pointers: Pointers<N>
+}
+
+impl<const N: usize> SmallCStringArray<N> {
+    /// Creates a new `SmallCStringArray` from a vector of strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::NulError` if any string contains an interior
+    /// null byte. Returns `CStringArrayError::EmptyArray` if the input vector
+    /// is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::SmallCStringArray;
+    ///
+    /// let array = SmallCStringArray::<8>::new(vec!["foo".to_string()]).unwrap();
+    /// assert_eq!(array.len(), 1);
+    /// ```
+    pub fn new(strings: Vec<String>) -> Result<Self, CStringArrayError> {
+        let cstrings: Vec<CString> = strings
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| CString::new(s).map_err(|e| CStringArrayError::nul_at(i, e)))
+            .collect::<Result<_, _>>()?;
+
+        Self::from_cstrings(cstrings)
+    }
+
+    /// Creates a new `SmallCStringArray` from a vector of `CString`s
+    /// (zero-copy).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CStringArrayError::EmptyArray` if the input vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::SmallCStringArray;
+    ///
+    /// let array = SmallCStringArray::<2>::from_cstrings(vec![CString::new("a").unwrap()]).unwrap();
+    /// assert!(array.is_inline());
+    /// ```
+    pub fn from_cstrings(strings: Vec<CString>) -> Result<Self, CStringArrayError> {
+        if strings.is_empty() {
+            return Err(EmptyArray);
+        }
+
+        Ok(Self::from_cstrings_allow_empty(strings))
+    }
+
+    /// Creates an empty `SmallCStringArray` whose inline pointer table holds
+    /// only the null terminator. Does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::SmallCStringArray;
+    ///
+    /// let mut array = SmallCStringArray::<4>::empty();
+    /// array.push(CString::new("prog").unwrap());
+    /// assert_eq!(array.len(), 1);
+    /// ```
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            strings:  Vec::new(),
+            pointers: Pointers::Inline(PointerTable::new())
+        }
+    }
+
+    fn from_cstrings_allow_empty(strings: Vec<CString>) -> Self {
+        let pointers = if strings.len() <= N {
+            let mut table = PointerTable::new();
+            for (slot, s) in table.slots_mut().iter_mut().zip(&strings) {
+                *slot = s.as_ptr();
+            }
+            Pointers::Inline(table)
+        } else {
+            let mut pointers: Vec<*const c_char> = Vec::with_capacity(strings.len() + 1);
+            pointers.extend(strings.iter().map(|s| s.as_ptr()));
+            pointers.push(null());
+            Pointers::Heap(pointers)
+        };
+
+        Self {
+            strings,
+            pointers
+        }
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `char**`.
+    ///
+    /// The pointer array is null-terminated. It is valid until this array is
+    /// moved, modified or dropped.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const *const c_char {
+        match &self.pointers {
+            Pointers::Inline(table) => table.as_ptr(),
+            Pointers::Heap(pointers) => pointers.as_ptr()
+        }
+    }
+
+    /// Returns `true` while the pointer table is stored inline.
+    #[inline]
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.pointers, Pointers::Inline(_))
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns a reference to the `CString` at the specified index, or `None`
+    /// if the index is out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&CString> {
+        self.strings.get(index)
+    }
+
+    /// Returns an iterator over the `CString` references.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, CString> {
+        self.strings.iter()
+    }
+
+    /// Returns a slice of the underlying `CString` array.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[CString] {
+        &self.strings
+    }
+
+    /// Consumes the array and returns the underlying vector of `CString`s.
+    #[inline]
+    #[must_use]
+    pub fn into_strings(self) -> Vec<CString> {
+        self.strings
+    }
+
+    /// Appends a string, moving the pointer table to the heap if the array
+    /// grows past `N` strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    ///
+    /// use cstring_array::SmallCStringArray;
+    ///
+    /// let mut array =
+    ///     SmallCStringArray::<1>::from_cstrings(vec![CString::new("a").unwrap()]).unwrap();
+    /// assert!(array.is_inline());
+    ///
+    /// array.push(CString::new("b").unwrap());
+    /// assert!(!array.is_inline());
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    pub fn push(&mut self, value: CString) {
+        let len = self.strings.len();
+        match &mut self.pointers {
+            Pointers::Inline(table) if len < N => table.slots_mut()[len] = value.as_ptr(),
+            Pointers::Inline(table) => {
+                let mut pointers: Vec<*const c_char> = Vec::with_capacity(2 * N + 2);
+                pointers.extend_from_slice(table.slots());
+                pointers.push(value.as_ptr());
+                pointers.push(null());
+                self.pointers = Pointers::Heap(pointers);
+            }
+            Pointers::Heap(pointers) => {
+                pointers[len] = value.as_ptr();
+                pointers.push(null());
+            }
+        }
+        self.strings.push(value);
+    }
+
+    /// Removes the last string and returns it, or `None` if the array is
+    /// empty.
+    pub fn pop(&mut self) -> Option<CString> {
+        let value = self.strings.pop()?;
+        let len = self.strings.len();
+        match &mut self.pointers {
+            Pointers::Inline(table) => table.slots_mut()[len] = null(),
+            Pointers::Heap(pointers) => {
+                pointers.swap_remove(len);
+            }
+        }
+        Some(value)
+    }
+}
+
+unsafe impl<const N: usize> Send for SmallCStringArray<N> {}
+unsafe impl<const N: usize> Sync for SmallCStringArray<N> {}
+
+// ============================================================================
+// Standard Traits
+// ============================================================================
+
+impl<const N: usize> Clone for SmallCStringArray<N> {
+    fn clone(&self) -> Self {
+        Self::from_cstrings_allow_empty(self.strings.clone())
+    }
+}
+
+impl<const N: usize> Default for SmallCStringArray<N> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<const N: usize> PartialEq for SmallCStringArray<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.strings == other.strings
+    }
+}
+
+impl<const N: usize> Eq for SmallCStringArray<N> {}
+
+impl<const N: usize> core::ops::Index<usize> for SmallCStringArray<N> {
+    type Output = CString;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.strings[index]
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a SmallCStringArray<N> {
+    type Item = &'a CString;
+    type IntoIter = Iter<'a, CString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const N: usize> From<SmallCStringArray<N>> for CStringArray {
+    fn from(array: SmallCStringArray<N>) -> Self {
+        CStringArray::from_cstrings_allow_empty(array.into_strings())
+    }
+}
+
+impl<const N: usize> From<CStringArray> for SmallCStringArray<N> {
+    fn from(array: CStringArray) -> Self {
+        Self::from_cstrings_allow_empty(array.into_strings())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    fn assert_pointer_table<const N: usize>(array: &SmallCStringArray<N>, expected: &[&str]) {
+        assert_eq!(array.len(), expected.len());
+
+        let ptr = array.as_ptr();
+        unsafe {
+            for (i, value) in expected.iter().enumerate() {
+                assert_eq!(CStr::from_ptr(*ptr.add(i)).to_str().unwrap(), *value);
+            }
+            assert!((*ptr.add(expected.len())).is_null());
+        }
+    }
+
+    fn cstrings(values: &[&str]) -> Vec<CString> {
+        values.iter().map(|s| CString::new(*s).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_inline_up_to_capacity() {
+        let array = SmallCStringArray::<3>::from_cstrings(cstrings(&["a", "b", "c"])).unwrap();
+        assert!(array.is_inline());
+        assert_pointer_table(&array, &["a", "b", "c"]);
+
+        let array =
+            SmallCStringArray::<3>::from_cstrings(cstrings(&["a", "b", "c", "d"])).unwrap();
+        assert!(!array.is_inline());
+        assert_pointer_table(&array, &["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_push_spills_and_pop() {
+        let mut array = SmallCStringArray::<2>::empty();
+        assert_pointer_table(&array, &[]);
+
+        array.push(CString::new("a").unwrap());
+        array.push(CString::new("b").unwrap());
+        assert!(array.is_inline());
+        assert_pointer_table(&array, &["a", "b"]);
+
+        array.push(CString::new("c").unwrap());
+        assert!(!array.is_inline());
+        assert_pointer_table(&array, &["a", "b", "c"]);
+
+        assert_eq!(array.pop().unwrap().to_str().unwrap(), "c");
+        assert_pointer_table(&array, &["a", "b"]);
+    }
+
+    #[test]
+    fn test_pop_inline() {
+        let mut array =
+            SmallCStringArray::<4>::new(vec!["x".to_string(), "y".to_string()]).unwrap();
+        assert_eq!(array.pop().unwrap().to_str().unwrap(), "y");
+        assert_pointer_table(&array, &["x"]);
+        array.pop();
+        assert!(array.pop().is_none());
+        assert_pointer_table(&array, &[]);
+    }
+
+    #[test]
+    fn test_pointer_valid_after_move() {
+        let array = SmallCStringArray::<4>::new(vec!["moved".to_string()]).unwrap();
+        let moved = Box::new(array);
+        assert_pointer_table(&moved, &["moved"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            SmallCStringArray::<4>::new(Vec::new()),
+            Err(EmptyArray)
+        ));
+        let err =
+            SmallCStringArray::<4>::new(vec!["a".to_string(), "\0".to_string()]).unwrap_err();
+        assert_eq!(err.index(), Some(1));
+    }
+
+    #[test]
+    fn test_clone_and_conversions() {
+        let array = SmallCStringArray::<2>::new(vec!["a".to_string(), "b".to_string()]).unwrap();
+        let cloned = array.clone();
+        assert_eq!(array, cloned);
+        assert_pointer_table(&cloned, &["a", "b"]);
+
+        let regular = CStringArray::from(cloned);
+        assert_eq!(regular.len(), 2);
+
+        let small = SmallCStringArray::<1>::from(regular);
+        assert!(!small.is_inline());
+        assert_pointer_table(&small, &["a", "b"]);
+    }
+}
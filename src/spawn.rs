@@ -0,0 +1,455 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! A builder for `posix_spawn`.
+//!
+//! [`PosixSpawn`] owns the argument vector, an optional environment, and the
+//! `posix_spawn_file_actions_t` and `posix_spawnattr_t` objects describing
+//! how the child is set up. The C objects are initialized when the builder
+//! is created and destroyed when it is dropped, so the same builder can
+//! spawn any number of children.
+//!
+//! Requires the `spawn` feature.
+
+use std::{
+    ffi::{CStr, c_char, c_int, c_short},
+    fmt,
+    io::Error,
+    mem::MaybeUninit
+};
+
+use libc::{mode_t, pid_t, posix_spawn_file_actions_t, posix_spawnattr_t, sigset_t};
+
+use crate::{
+    array::CStringArray,
+    env::EnvArray,
+    sys::{check, envp}
+};
+
+/// Builder spawning child processes with `posix_spawn`.
+///
+/// File actions are applied in the child in the order they were added.
+///
+/// # Example
+///
+/// ```no_run
+/// use cstring_array::{CStringArray, EnvArray, spawn::PosixSpawn};
+///
+/// let argv = CStringArray::try_from(["ls", "-l"]).unwrap();
+/// let mut spawn = PosixSpawn::new(argv).unwrap();
+/// spawn
+///     .env(EnvArray::from_pairs([("LC_ALL", "C")]).unwrap())
+///     .close(0)
+///     .unwrap()
+///     .process_group(0)
+///     .unwrap();
+///
+/// let pid = spawn.spawnp(c"ls").unwrap();
+/// ```
+pub struct PosixSpawn {
+    argv:    CStringArray,
+    env:     Option<EnvArray>,
+    actions: FileActions,
+    attr:    SpawnAttr,
+    flags:   c_short
+}
+
+impl PosixSpawn {
+    /// Creates a builder for the given argument vector.
+    ///
+    /// The child inherits the environment of the current process unless
+    /// [`env`](Self::env) is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `InvalidInput` if `argv` is empty, and the
+    /// system error if the file actions or attributes cannot be initialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, spawn::PosixSpawn};
+    ///
+    /// let spawn = PosixSpawn::new(CStringArray::try_from(["true"]).unwrap());
+    /// assert!(spawn.is_ok());
+    ///
+    /// assert!(PosixSpawn::new(CStringArray::empty()).is_err());
+    /// ```
+    pub fn new(argv: CStringArray) -> Result<Self, Error> {
+        check(&argv, None)?;
+        Ok(Self {
+            argv,
+            env: None,
+            actions: FileActions::new()?,
+            attr: SpawnAttr::new()?,
+            flags: 0
+        })
+    }
+
+    /// Sets the environment of the child.
+    pub fn env(&mut self, env: EnvArray) -> &mut Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Duplicates `fd` onto `new_fd` in the child.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the action cannot be added, for example
+    /// when a descriptor is negative.
+    pub fn dup2(&mut self, fd: c_int, new_fd: c_int) -> Result<&mut Self, Error> {
+        // SAFETY: `actions` is initialized.
+        cvt(unsafe {
+            libc::posix_spawn_file_actions_adddup2(self.actions.as_mut_ptr(), fd, new_fd)
+        })?;
+        Ok(self)
+    }
+
+    /// Opens `path` as `fd` in the child, with the `open` flags `oflag` and
+    /// permissions `mode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the action cannot be added.
+    pub fn open(
+        &mut self,
+        fd: c_int,
+        path: &CStr,
+        oflag: c_int,
+        mode: mode_t
+    ) -> Result<&mut Self, Error> {
+        // SAFETY: `actions` is initialized; the path is copied by the call.
+        cvt(unsafe {
+            libc::posix_spawn_file_actions_addopen(
+                self.actions.as_mut_ptr(),
+                fd,
+                path.as_ptr(),
+                oflag,
+                mode
+            )
+        })?;
+        Ok(self)
+    }
+
+    /// Closes `fd` in the child.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the action cannot be added.
+    pub fn close(&mut self, fd: c_int) -> Result<&mut Self, Error> {
+        // SAFETY: `actions` is initialized.
+        cvt(unsafe { libc::posix_spawn_file_actions_addclose(self.actions.as_mut_ptr(), fd) })?;
+        Ok(self)
+    }
+
+    /// Changes the working directory of the child to `path`.
+    ///
+    /// Relative paths in later actions, and the program path of
+    /// [`spawn`](Self::spawn), are resolved against the new directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the action cannot be added.
+    #[cfg(any(
+        all(target_os = "linux", any(target_env = "gnu", target_env = "musl")),
+        target_os = "macos"
+    ))]
+    pub fn chdir(&mut self, path: &CStr) -> Result<&mut Self, Error> {
+        // SAFETY: `actions` is initialized; the path is copied by the call.
+        cvt(unsafe {
+            libc::posix_spawn_file_actions_addchdir_np(self.actions.as_mut_ptr(), path.as_ptr())
+        })?;
+        Ok(self)
+    }
+
+    /// Sets the signal mask of the child.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the attribute cannot be set.
+    pub fn sigmask(&mut self, mask: &sigset_t) -> Result<&mut Self, Error> {
+        // SAFETY: `attr` is initialized.
+        cvt(unsafe { libc::posix_spawnattr_setsigmask(self.attr.as_mut_ptr(), mask) })?;
+        self.set_flag(libc::POSIX_SPAWN_SETSIGMASK as c_short)
+    }
+
+    /// Puts the child into the process group `pgroup`, or into a new group
+    /// led by the child when `pgroup` is `0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the attribute cannot be set.
+    pub fn process_group(&mut self, pgroup: pid_t) -> Result<&mut Self, Error> {
+        // SAFETY: `attr` is initialized.
+        cvt(unsafe { libc::posix_spawnattr_setpgroup(self.attr.as_mut_ptr(), pgroup) })?;
+        self.set_flag(libc::POSIX_SPAWN_SETPGROUP as c_short)
+    }
+
+    /// Starts the child in a new session, as if it called `setsid`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the attribute cannot be set.
+    #[cfg(target_os = "linux")]
+    pub fn setsid(&mut self) -> Result<&mut Self, Error> {
+        self.set_flag(libc::POSIX_SPAWN_SETSID)
+    }
+
+    /// Spawns the program at `path` and returns the process ID of the child.
+    ///
+    /// `path` is used as is: it is not searched for in `PATH`. The caller is
+    /// responsible for waiting for the child.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the child cannot be spawned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::{CStringArray, spawn::PosixSpawn};
+    ///
+    /// let argv = CStringArray::try_from(["sh", "-c", "exit 3"]).unwrap();
+    /// let pid = PosixSpawn::new(argv).unwrap().spawn(c"/bin/sh").unwrap();
+    ///
+    /// let mut status = 0;
+    /// unsafe { libc::waitpid(pid, &mut status, 0) };
+    /// assert_eq!(libc::WEXITSTATUS(status), 3);
+    /// ```
+    pub fn spawn(&self, path: &CStr) -> Result<pid_t, Error> {
+        self.run(path, libc::posix_spawn)
+    }
+
+    /// Spawns `file`, searching for it in `PATH` when it contains no `/`,
+    /// and returns the process ID of the child.
+    ///
+    /// # Errors
+    ///
+    /// Returns the system error if the child cannot be spawned.
+    pub fn spawnp(&self, file: &CStr) -> Result<pid_t, Error> {
+        self.run(file, libc::posix_spawnp)
+    }
+
+    fn set_flag(&mut self, flag: c_short) -> Result<&mut Self, Error> {
+        // SAFETY: `attr` is initialized.
+        cvt(unsafe { libc::posix_spawnattr_setflags(self.attr.as_mut_ptr(), self.flags | flag) })?;
+        self.flags |= flag;
+        Ok(self)
+    }
+
+    fn run(&self, path: &CStr, f: SpawnFn) -> Result<pid_t, Error> {
+        check(&self.argv, self.env.as_ref())?;
+
+        let mut pid = 0;
+        // SAFETY: the file actions and attributes are initialized, and all
+        // arrays are null-terminated tables of C strings that outlive the
+        // call.
+        cvt(unsafe {
+            f(
+                &mut pid,
+                path.as_ptr(),
+                self.actions.as_ptr(),
+                self.attr.as_ptr(),
+                self.argv.as_ptr().cast(),
+                envp(self.env.as_ref()).cast()
+            )
+        })?;
+        Ok(pid)
+    }
+}
+
+impl fmt::Debug for PosixSpawn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PosixSpawn")
+            .field("argv", &self.argv)
+            .field("env", &self.env)
+            .field("flags", &self.flags)
+            .finish_non_exhaustive()
+    }
+}
+
+// SAFETY: the file actions and attributes are owned by the builder and only
+// reachable through it; `&self` methods only read them.
+unsafe impl Send for PosixSpawn {}
+unsafe impl Sync for PosixSpawn {}
+
+type SpawnFn = unsafe extern "C" fn(
+    *mut pid_t,
+    *const c_char,
+    *const posix_spawn_file_actions_t,
+    *const posix_spawnattr_t,
+    *const *mut c_char,
+    *const *mut c_char
+) -> c_int;
+
+/// Converts the error number returned by a `posix_spawn*` function.
+fn cvt(ret: c_int) -> Result<(), Error> {
+    match ret {
+        0 => Ok(()),
+        errno => Err(Error::from_raw_os_error(errno))
+    }
+}
+
+/// Defines an owned, heap-pinned C object with an init and a destroy
+/// function.
+macro_rules! owned {
+    ($name:ident, $ty:ty, $init:path, $destroy:path) => {
+        struct $name(Box<MaybeUninit<$ty>>);
+
+        impl $name {
+            fn new() -> Result<Self, Error> {
+                let mut inner = Box::new(MaybeUninit::uninit());
+                // SAFETY: `inner` is valid for writes.
+                cvt(unsafe { $init(inner.as_mut_ptr()) })?;
+                Ok(Self(inner))
+            }
+
+            fn as_ptr(&self) -> *const $ty {
+                self.0.as_ptr()
+            }
+
+            fn as_mut_ptr(&mut self) -> *mut $ty {
+                self.0.as_mut_ptr()
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                // SAFETY: initialized in `new` and destroyed only here.
+                unsafe { $destroy(self.0.as_mut_ptr()) };
+            }
+        }
+    };
+}
+
+owned!(
+    FileActions,
+    posix_spawn_file_actions_t,
+    libc::posix_spawn_file_actions_init,
+    libc::posix_spawn_file_actions_destroy
+);
+owned!(
+    SpawnAttr,
+    posix_spawnattr_t,
+    libc::posix_spawnattr_init,
+    libc::posix_spawnattr_destroy
+);
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{ErrorKind, Read},
+        os::fd::AsRawFd
+    };
+
+    use super::*;
+    use crate::sys::cloexec_pipe;
+
+    fn wait(pid: pid_t) -> c_int {
+        let mut status = 0;
+        assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+        assert!(libc::WIFEXITED(status));
+        libc::WEXITSTATUS(status)
+    }
+
+    #[test]
+    fn test_empty_argv_is_rejected() {
+        let err = PosixSpawn::new(CStringArray::empty()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_spawn_with_env() {
+        let argv = CStringArray::try_from(["sh", "-c", "exit $CODE"]).unwrap();
+        let mut spawn = PosixSpawn::new(argv).unwrap();
+        spawn.env(EnvArray::from_pairs([("CODE", "5")]).unwrap());
+
+        assert_eq!(wait(spawn.spawn(c"/bin/sh").unwrap()), 5);
+        assert_eq!(wait(spawn.spawnp(c"sh").unwrap()), 5);
+    }
+
+    #[test]
+    fn test_missing_program() {
+        let argv = CStringArray::try_from(["missing"]).unwrap();
+        let spawn = PosixSpawn::new(argv).unwrap();
+        match spawn.spawn(c"/nonexistent/program") {
+            Err(err) => assert_eq!(err.kind(), ErrorKind::NotFound),
+            // Implementations that report exec failures as exit status 127.
+            Ok(pid) => assert_eq!(wait(pid), 127)
+        }
+    }
+
+    #[test]
+    fn test_dup2_and_close() {
+        let (read, write) = cloexec_pipe();
+        let (read_fd, write_fd) = (read.as_raw_fd(), write.as_raw_fd());
+
+        let argv = CStringArray::try_from(["sh", "-c", "echo hello"]).unwrap();
+        let mut spawn = PosixSpawn::new(argv).unwrap();
+        spawn
+            .dup2(write_fd, 1)
+            .unwrap()
+            .close(read_fd)
+            .unwrap()
+            .close(write_fd)
+            .unwrap();
+        let pid = spawn.spawn(c"/bin/sh").unwrap();
+
+        drop(write);
+        let mut output = String::new();
+        File::from(read).read_to_string(&mut output).unwrap();
+        assert_eq!(wait(pid), 0);
+        assert_eq!(output, "hello\n");
+    }
+
+    #[test]
+    fn test_open() {
+        let argv = CStringArray::try_from(["sh", "-c", "read line"]).unwrap();
+        let mut spawn = PosixSpawn::new(argv).unwrap();
+        spawn.open(0, c"/dev/null", libc::O_RDONLY, 0).unwrap();
+        assert_eq!(wait(spawn.spawn(c"/bin/sh").unwrap()), 1);
+    }
+
+    #[test]
+    fn test_process_group() {
+        let argv = CStringArray::try_from(["sh", "-c", "exit 0"]).unwrap();
+        let mut spawn = PosixSpawn::new(argv).unwrap();
+        spawn.process_group(0).unwrap();
+
+        let pid = spawn.spawn(c"/bin/sh").unwrap();
+        let pgid = unsafe { libc::getpgid(pid) };
+        assert_eq!(wait(pid), 0);
+        assert_eq!(pgid, pid);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_chdir_and_setsid() {
+        let (read, write) = cloexec_pipe();
+        let (read_fd, write_fd) = (read.as_raw_fd(), write.as_raw_fd());
+
+        let argv = CStringArray::try_from(["sh", "-c", "read line; test \"$PWD\" = /"]).unwrap();
+        let mut spawn = PosixSpawn::new(argv).unwrap();
+        spawn
+            .chdir(c"/")
+            .unwrap()
+            .setsid()
+            .unwrap()
+            .dup2(read_fd, 0)
+            .unwrap()
+            .close(read_fd)
+            .unwrap()
+            .close(write_fd)
+            .unwrap();
+        let pid = spawn.spawn(c"/bin/sh").unwrap();
+
+        // The child blocks on the pipe until it is closed.
+        let sid = unsafe { libc::getsid(pid) };
+        drop((read, write));
+        assert_eq!(wait(pid), 0);
+        assert_eq!(sid, pid);
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Const-constructible string arrays over `'static` C string literals.
+//!
+//! [`StaticCStrArray`] builds its null-terminated pointer table at compile
+//! time without any heap allocation, so fixed argument lists and option
+//! tables made of `c"..."` literals can live in a `static`.
+
+use core::{
+    ffi::{CStr, c_char},
+    iter::Copied,
+    slice::Iter
+};
+
+use crate::inline::PointerTable;
+
+/// Null-terminated `char**` table over `N` static C strings, built in `const`
+/// context.
+///
+/// Both the strings and the pointer table are part of the value, so no heap
+/// allocation is involved. When stored in a `static`, the pointer returned by
+/// [`as_ptr`](Self::as_ptr) is valid for the whole life of the program and
+/// can be handed to C libraries that keep it.
+///
+/// Declare arrays whose pointer escapes as `static`, not `const`: every use
+/// of a `const` item creates a fresh temporary, and a pointer into it
+/// dangles once the temporary is dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::ffi::c_char;
+///
+/// use cstring_array::StaticCStrArray;
+///
+/// static ARGS: StaticCStrArray<2> = StaticCStrArray::new([c"--verbose", c"--color=never"]);
+///
+/// let ptr: *const *const c_char = ARGS.as_ptr();
+/// assert_eq!(ARGS.len(), 2);
+/// assert_eq!(ARGS.get(1), Some(c"--color=never"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StaticCStrArray<const N: usize> {
+    table:   PointerTable<N>,
+    strings: [&'static CStr; N]
+}
+
+impl<const N: usize> StaticCStrArray<N> {
+    /// Creates the array and its pointer table from static C strings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cstring_array::StaticCStrArray;
+    ///
+    /// const OPTIONS: StaticCStrArray<3> = StaticCStrArray::new([c"-a", c"-b", c"-c"]);
+    /// assert_eq!(OPTIONS.len(), 3);
+    /// ```
+    #[must_use]
+    pub const fn new(strings: [&'static CStr; N]) -> Self {
+        let mut table = PointerTable::new();
+        let mut i = 0;
+        while i < N {
+            table.slots_mut()[i] = strings[i].as_ptr();
+            i += 1;
+        }
+
+        Self {
+            table,
+            strings
+        }
+    }
+
+    /// Returns a pointer suitable for passing to C functions expecting
+    /// `char**`.
+    ///
+    /// The pointer array is null-terminated. It points into this value: for a
+    /// `static` it is valid forever, otherwise until the value is moved or
+    /// dropped.
+    #[inline]
+    #[must_use]
+    pub const fn as_ptr(&self) -> *const *const c_char {
+        self.table.as_ptr()
+    }
+
+    /// Returns the number of strings in the array.
+    ///
+    /// This count does not include the null terminator.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the array contains no strings.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the string at the specified index, or `None` if the index is
+    /// out of bounds.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, index: usize) -> Option<&'static CStr> {
+        if index < N {
+            Some(self.strings[index])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the strings.
+    #[inline]
+    pub fn iter(&self) -> Copied<Iter<'_, &'static CStr>> {
+        self.strings.iter().copied()
+    }
+
+    /// Returns the strings as a slice.
+    #[inline]
+    #[must_use]
+    pub const fn as_slice(&self) -> &[&'static CStr] {
+        &self.strings
+    }
+}
+
+// SAFETY: every pointer in the table refers to immutable `'static` string
+// data, and the table itself is never mutated after construction.
+unsafe impl<const N: usize> Send for StaticCStrArray<N> {}
+unsafe impl<const N: usize> Sync for StaticCStrArray<N> {}
+
+// ============================================================================
+// Standard Traits
+// ============================================================================
+
+impl<const N: usize> PartialEq for StaticCStrArray<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.strings == other.strings
+    }
+}
+
+impl<const N: usize> Eq for StaticCStrArray<N> {}
+
+impl<const N: usize> core::ops::Index<usize> for StaticCStrArray<N> {
+    type Output = CStr;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.strings[index]
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a StaticCStrArray<N> {
+    type Item = &'static CStr;
+    type IntoIter = Copied<Iter<'a, &'static CStr>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static ARGV: StaticCStrArray<3> = StaticCStrArray::new([c"prog", c"-x", c""]);
+
+    #[test]
+    fn test_static_pointer_table() {
+        let ptr = ARGV.as_ptr();
+        unsafe {
+            assert_eq!(CStr::from_ptr(*ptr), c"prog");
+            assert_eq!(CStr::from_ptr(*ptr.add(1)), c"-x");
+            assert_eq!(CStr::from_ptr(*ptr.add(2)), c"");
+            assert!((*ptr.add(3)).is_null());
+        }
+        assert_eq!(ptr, ARGV.as_ptr());
+    }
+
+    #[test]
+    fn test_accessors() {
+        const LEN: usize = ARGV.len();
+        assert_eq!(LEN, 3);
+        assert!(!ARGV.is_empty());
+        assert_eq!(ARGV.get(0), Some(c"prog"));
+        assert_eq!(ARGV.get(3), None);
+        assert_eq!(&ARGV[1], c"-x");
+        assert_eq!(ARGV.iter().collect::<Vec<_>>(), [c"prog", c"-x", c""]);
+    }
+
+    #[test]
+    fn test_empty() {
+        static EMPTY: StaticCStrArray<0> = StaticCStrArray::new([]);
+        assert!(EMPTY.is_empty());
+        assert!(unsafe { (*EMPTY.as_ptr()).is_null() });
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        fn assert_sync<T: Sync>(_: &T) {}
+        assert_sync(&ARGV);
+
+        let handle = std::thread::spawn(|| ARGV.get(0).unwrap().to_owned());
+        assert_eq!(handle.join().unwrap().as_c_str(), c"prog");
+    }
+}
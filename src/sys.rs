@@ -0,0 +1,85 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm <andrey.rozanov.vl@gmail.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Checks and helpers shared by the `exec` and `spawn` modules.
+
+use std::{
+    ffi::c_char,
+    io::{Error, ErrorKind}
+};
+
+use crate::{array::CStringArray, env::EnvArray};
+
+unsafe extern "C" {
+    static environ: *const *const c_char;
+}
+
+/// Checks that `argv` holds the program name and that both pointer tables
+/// are null-terminated.
+pub(crate) fn check(argv: &CStringArray, env: Option<&EnvArray>) -> Result<(), Error> {
+    if argv.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "argv must contain at least the program name"
+        ));
+    }
+
+    // SAFETY: both tables hold `len + 1` entries.
+    let terminated = unsafe {
+        (*argv.as_ptr().add(argv.len())).is_null()
+            && env.is_none_or(|env| (*env.as_envp().add(env.len())).is_null())
+    };
+    if !terminated {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "pointer array is not null-terminated"
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the `envp` to pass: the given environment, or the current one.
+pub(crate) fn envp(env: Option<&EnvArray>) -> *const *const c_char {
+    // SAFETY: `environ` is only read, never written, by this crate.
+    env.map_or_else(|| unsafe { environ }, EnvArray::as_envp)
+}
+
+/// Creates a pipe whose ends are closed on `exec`, for tests that fork or
+/// spawn while other tests do the same.
+#[cfg(test)]
+pub(crate) fn cloexec_pipe() -> (std::os::fd::OwnedFd, std::os::fd::OwnedFd) {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let mut fds = [0; 2];
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    ))]
+    assert_eq!(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) }, 0);
+    // Without `pipe2`, a child forked between the two calls can still
+    // inherit the pipe.
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    )))]
+    {
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        for fd in fds {
+            assert_eq!(
+                unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) },
+                0
+            );
+        }
+    }
+    // SAFETY: both descriptors were just opened and are owned by no one else.
+    unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) }
+}
@@ -47,6 +47,8 @@ fn test_new_with_interior_null() {
     match result {
         Err(NulError(e)) => {
             assert_eq!(e.nul_position(), 2);
+            assert_eq!(e.index(), Some(1));
+            assert_eq!(e.preview(), "wo\\x00rld");
         }
         _ => panic!("Expected NulError")
     }
@@ -333,3 +335,407 @@ fn test_empty_strings_allowed() {
     assert_eq!(array.get(1).unwrap().to_str().unwrap(), "non-empty");
     assert_eq!(array.get(2).unwrap().to_str().unwrap(), "");
 }
+
+fn assert_pointer_table(array: &CStringArray, expected: &[&str]) {
+    assert_eq!(array.len(), expected.len());
+
+    let ptr = array.as_ptr();
+    unsafe {
+        for (i, value) in expected.iter().enumerate() {
+            let item = *ptr.add(i);
+            assert_eq!(item, array[i].as_ptr());
+            assert_eq!(std::ffi::CStr::from_ptr(item).to_str().unwrap(), *value);
+        }
+        assert!((*ptr.add(expected.len())).is_null());
+    }
+}
+
+#[test]
+fn test_push_updates_pointer_table() {
+    let mut array = CStringArray::new(vec!["gcc".to_string()]).unwrap();
+    array.push(CString::new("-O2").unwrap());
+    array.push(CString::new("main.c").unwrap());
+
+    assert_pointer_table(&array, &["gcc", "-O2", "main.c"]);
+}
+
+#[test]
+fn test_insert_and_remove() {
+    let mut array = CStringArray::try_from(["a", "c"]).unwrap();
+    array.insert(1, CString::new("b").unwrap());
+    array.insert(0, CString::new("start").unwrap());
+    array.insert(4, CString::new("end").unwrap());
+    assert_pointer_table(&array, &["start", "a", "b", "c", "end"]);
+
+    assert_eq!(array.remove(0).to_str().unwrap(), "start");
+    assert_eq!(array.remove(3).to_str().unwrap(), "end");
+    assert_pointer_table(&array, &["a", "b", "c"]);
+}
+
+#[test]
+#[should_panic]
+fn test_insert_out_of_bounds() {
+    let mut array = CStringArray::try_from(["a"]).unwrap();
+    array.insert(2, CString::new("b").unwrap());
+}
+
+#[test]
+#[should_panic]
+fn test_remove_out_of_bounds() {
+    let mut array = CStringArray::try_from(["a"]).unwrap();
+    array.remove(1);
+}
+
+#[test]
+fn test_pop_until_empty() {
+    let mut array = CStringArray::try_from(["a", "b"]).unwrap();
+
+    assert_eq!(array.pop().unwrap().to_str().unwrap(), "b");
+    assert_pointer_table(&array, &["a"]);
+
+    assert_eq!(array.pop().unwrap().to_str().unwrap(), "a");
+    assert!(array.is_empty());
+    assert_pointer_table(&array, &[]);
+
+    assert!(array.pop().is_none());
+
+    array.push(CString::new("again").unwrap());
+    assert_pointer_table(&array, &["again"]);
+}
+
+#[test]
+fn test_truncate_and_clear() {
+    let mut array = CStringArray::try_from(["a", "b", "c", "d"]).unwrap();
+
+    array.truncate(10);
+    assert_pointer_table(&array, &["a", "b", "c", "d"]);
+
+    array.truncate(2);
+    assert_pointer_table(&array, &["a", "b"]);
+
+    array.clear();
+    assert_pointer_table(&array, &[]);
+
+    let cloned = array.clone();
+    assert!(cloned.is_empty());
+}
+
+#[test]
+fn test_retain_updates_pointer_table() {
+    let mut array = CStringArray::try_from(["keep1", "drop", "keep2", "drop"]).unwrap();
+    array.retain(|s| s.to_bytes().starts_with(b"keep"));
+    assert_pointer_table(&array, &["keep1", "keep2"]);
+
+    array.retain(|_| false);
+    assert_pointer_table(&array, &[]);
+}
+
+#[test]
+fn test_sort_and_dedup_envp() {
+    let mut array =
+        CStringArray::try_from(["PATH=/bin", "HOME=/root", "A=1", "HOME=/root"]).unwrap();
+    array.sort();
+    array.dedup();
+    assert_pointer_table(&array, &["A=1", "HOME=/root", "PATH=/bin"]);
+}
+
+#[test]
+fn test_sort_by_key_and_dedup_by_key() {
+    let mut array = CStringArray::try_from(["bb", "a", "ccc", "dd"]).unwrap();
+    array.sort_by_key(|s| s.to_bytes().len());
+    assert_pointer_table(&array, &["a", "bb", "dd", "ccc"]);
+
+    array.dedup_by_key(|s| s.to_bytes().len());
+    assert_pointer_table(&array, &["a", "bb", "ccc"]);
+}
+
+#[test]
+fn test_reverse_and_swap() {
+    let mut array = CStringArray::try_from(["a", "b", "c"]).unwrap();
+    array.reverse();
+    assert_pointer_table(&array, &["c", "b", "a"]);
+
+    array.swap(0, 1);
+    assert_pointer_table(&array, &["b", "c", "a"]);
+}
+
+#[test]
+fn test_drain_ranges() {
+    let mut array = CStringArray::try_from(["a", "b", "c", "d", "e"]).unwrap();
+
+    let drained: Vec<CString> = array.drain(1..=2).collect();
+    assert_eq!(
+        drained,
+        vec![CString::new("b").unwrap(), CString::new("c").unwrap()]
+    );
+    assert_pointer_table(&array, &["a", "d", "e"]);
+
+    drop(array.drain(2..));
+    assert_pointer_table(&array, &["a", "d"]);
+
+    assert_eq!(array.drain(..).count(), 2);
+    assert_pointer_table(&array, &[]);
+}
+
+#[test]
+fn test_drain_leaked() {
+    let mut array = CStringArray::try_from(["a", "b", "c", "d"]).unwrap();
+
+    let mut drain = array.drain(1..2);
+    assert_eq!(drain.next_back(), Some(CString::new("b").unwrap()));
+    std::mem::forget(drain);
+    assert_pointer_table(&array, &["a"]);
+
+    array.push(CString::new("e").unwrap());
+    assert_pointer_table(&array, &["a", "e"]);
+}
+
+#[test]
+#[should_panic]
+fn test_drain_out_of_bounds() {
+    let mut array = CStringArray::try_from(["a", "b"]).unwrap();
+    array.drain(1..3);
+}
+
+#[test]
+fn test_empty_constructors() {
+    let empty = CStringArray::empty();
+    assert!(empty.is_empty());
+    assert_pointer_table(&empty, &[]);
+
+    let default = CStringArray::default();
+    assert_eq!(default, empty);
+
+    let from_strings = CStringArray::new_allow_empty(Vec::new()).unwrap();
+    assert_pointer_table(&from_strings, &[]);
+
+    let from_cstrings = CStringArray::from_cstrings_allow_empty(Vec::new());
+    assert_pointer_table(&from_cstrings, &[]);
+}
+
+#[test]
+fn test_new_allow_empty_with_values() {
+    let array = CStringArray::new_allow_empty(vec!["a".to_string(), "b".to_string()]).unwrap();
+    assert_pointer_table(&array, &["a", "b"]);
+
+    match CStringArray::new_allow_empty(vec!["a\0".to_string()]) {
+        Err(NulError(e)) => assert_eq!(e.nul_position(), 1),
+        _ => panic!("Expected NulError")
+    }
+}
+
+#[test]
+fn test_with_capacity_then_push() {
+    let mut array = CStringArray::with_capacity(4);
+    assert_pointer_table(&array, &[]);
+
+    array.extend(vec![CString::new("x").unwrap(), CString::new("y").unwrap()]);
+    assert_pointer_table(&array, &["x", "y"]);
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn test_from_os_strings_non_utf8() {
+    use std::{
+        ffi::{OsStr, OsString},
+        os::unix::ffi::{OsStrExt, OsStringExt},
+        path::{Path, PathBuf}
+    };
+
+    let raw = b"file_\xff\xfe.txt";
+    let strings = vec![OsString::from("cat"), OsString::from_vec(raw.to_vec())];
+    let array = CStringArray::try_from(strings).unwrap();
+    assert_eq!(array[1].as_bytes(), raw);
+    assert!(array[1].to_str().is_err());
+
+    let slices = [OsStr::new("a"), OsStr::from_bytes(raw)];
+    let array = CStringArray::try_from(&slices[..]).unwrap();
+    assert_eq!(array[1].as_bytes(), raw);
+
+    let paths = vec![PathBuf::from("/tmp"), PathBuf::from(OsStr::from_bytes(raw))];
+    let array = CStringArray::try_from(paths).unwrap();
+    assert_eq!(array[1].as_bytes(), raw);
+
+    let array = CStringArray::from_os_strings([Path::new("/bin"), Path::new("/usr/bin")]).unwrap();
+    assert_pointer_table(&array, &["/bin", "/usr/bin"]);
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn test_from_os_strings_errors() {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+    let strings = vec![OsString::from_vec(b"bad\0name".to_vec())];
+    match CStringArray::try_from(strings) {
+        Err(NulError(e)) => assert_eq!(e.nul_position(), 3),
+        _ => panic!("Expected NulError")
+    }
+
+    let empty: Vec<OsString> = Vec::new();
+    assert!(matches!(CStringArray::try_from(empty), Err(EmptyArray)));
+}
+
+#[test]
+fn test_from_bytes_vec() {
+    let array = CStringArray::from_bytes_vec(vec![b"abc".to_vec(), vec![0xc3, 0x28]]).unwrap();
+    assert_eq!(array[0].to_str().unwrap(), "abc");
+    assert_eq!(array[1].as_bytes(), &[0xc3, 0x28]);
+
+    let slices: [&[u8]; 2] = [b"x", b""];
+    let array = CStringArray::try_from(&slices[..]).unwrap();
+    assert_pointer_table(&array, &["x", ""]);
+
+    match CStringArray::try_from(vec![b"a\0".to_vec()]) {
+        Err(NulError(e)) => assert_eq!(e.nul_position(), 1),
+        _ => panic!("Expected NulError")
+    }
+    assert!(matches!(
+        CStringArray::from_bytes_vec(Vec::new()),
+        Err(EmptyArray)
+    ));
+}
+
+#[test]
+fn test_from_vec_with_nul() {
+    let input = vec![b"first\0".to_vec(), b"\0".to_vec()];
+    let data_ptr = input[0].as_ptr();
+    let array = CStringArray::from_vec_with_nul(input).unwrap();
+    assert_pointer_table(&array, &["first", ""]);
+    assert_eq!(array[0].as_ptr().cast(), data_ptr);
+
+    assert!(matches!(
+        CStringArray::from_vec_with_nul(vec![b"no terminator".to_vec()]),
+        Err(FromVecWithNulError { .. })
+    ));
+    assert!(matches!(
+        CStringArray::from_vec_with_nul(vec![b"in\0ner\0".to_vec()]),
+        Err(FromVecWithNulError { .. })
+    ));
+    assert!(matches!(
+        CStringArray::from_vec_with_nul(Vec::new()),
+        Err(EmptyArray)
+    ));
+}
+
+#[test]
+fn test_try_from_iter_item_types() {
+    let from_strings =
+        CStringArray::try_from_iter(vec!["a".to_string(), "b".to_string()]).unwrap();
+    let from_strs = CStringArray::try_from_iter(["a", "b"]).unwrap();
+    let from_bytes = CStringArray::try_from_iter(vec![b"a".to_vec(), b"b".to_vec()]).unwrap();
+    let from_cstrings =
+        CStringArray::try_from_iter([CString::new("a").unwrap(), CString::new("b").unwrap()])
+            .unwrap();
+
+    assert_pointer_table(&from_strings, &["a", "b"]);
+    assert_eq!(from_strings, from_strs);
+    assert_eq!(from_strings, from_bytes);
+    assert_eq!(from_strings, from_cstrings);
+}
+
+#[test]
+fn test_try_from_iter_errors() {
+    assert!(matches!(
+        CStringArray::try_from_iter(std::iter::empty::<&str>()),
+        Err(EmptyArray)
+    ));
+
+    match CStringArray::try_from_iter(["fine", "bro\0ken"]) {
+        Err(NulError(e)) => assert_eq!(e.nul_position(), 3),
+        _ => panic!("Expected NulError")
+    }
+}
+
+#[test]
+fn test_try_from_iter_preallocates() {
+    let array = CStringArray::try_from_iter((0..100).map(|i| i.to_string())).unwrap();
+    assert_eq!(array.len(), 100);
+    assert_eq!(array[99].to_str().unwrap(), "99");
+}
+
+#[test]
+fn test_errors_report_element_index() {
+    let err = CStringArray::from_bytes_vec(vec![b"a".to_vec(), b"b".to_vec(), b"\0".to_vec()])
+        .unwrap_err();
+    assert_eq!(err.index(), Some(2));
+
+    let err = CStringArray::try_from_iter(["a", "b\0"]).unwrap_err();
+    assert_eq!(err.index(), Some(1));
+    assert_eq!(
+        format!("{}", err),
+        "Element 1 contains interior null byte at position 1 (\"b\\x00\")"
+    );
+
+    let err = CStringArray::from_vec_with_nul(vec![b"a\0".to_vec(), b"b".to_vec()]).unwrap_err();
+    assert_eq!(err.index(), Some(1));
+
+    assert_eq!(EmptyArray.index(), None);
+}
+
+#[test]
+fn test_new_recoverable_returns_input_unchanged() {
+    let input = vec![
+        "a".to_string(),
+        "b".to_string(),
+        "c\0".to_string(),
+        "d\0".to_string(),
+    ];
+    let err = CStringArray::new_recoverable(input.clone()).unwrap_err();
+
+    assert_eq!(err.index(), Some(2));
+    assert!(matches!(err.error(), NulError(e) if e.nul_position() == 1));
+    assert_eq!(err.into_inner(), input);
+}
+
+#[test]
+fn test_new_recoverable_retry_after_fix() {
+    let err = CStringArray::new_recoverable(vec![b"x\0".to_vec(), b"y".to_vec()]).unwrap_err();
+    let (_, mut input) = err.into_parts();
+    input[0].retain(|&b| b != 0);
+
+    let array = CStringArray::new_recoverable(input).unwrap();
+    assert_pointer_table(&array, &["x", "y"]);
+}
+
+#[test]
+fn test_new_recoverable_empty_and_valid() {
+    let err = CStringArray::new_recoverable(Vec::<String>::new()).unwrap_err();
+    assert!(matches!(err.error(), EmptyArray));
+    assert!(err.into_inner().is_empty());
+
+    let array = CStringArray::new_recoverable(vec!["p", "q"]).unwrap();
+    assert_pointer_table(&array, &["p", "q"]);
+}
+
+#[test]
+fn test_new_lossy_policies() {
+    use std::num::NonZeroU8;
+
+    use crate::NulPolicy;
+
+    let input = || vec!["clean".to_string(), "a\0b\0c".to_string(), "\0".to_string()];
+
+    let (array, changed) = CStringArray::new_lossy(input(), NulPolicy::Truncate).unwrap();
+    assert_eq!(changed, 2);
+    assert_pointer_table(&array, &["clean", "a", ""]);
+
+    let (array, changed) = CStringArray::new_lossy(input(), NulPolicy::Strip).unwrap();
+    assert_eq!(changed, 2);
+    assert_pointer_table(&array, &["clean", "abc", ""]);
+
+    let space = NonZeroU8::new(b' ').unwrap();
+    let (array, changed) = CStringArray::new_lossy(input(), NulPolicy::Replace(space)).unwrap();
+    assert_eq!(changed, 2);
+    assert_pointer_table(&array, &["clean", "a b c", " "]);
+}
+
+#[test]
+fn test_new_lossy_clean_and_empty() {
+    let (array, changed) =
+        CStringArray::new_lossy(vec![b"x".to_vec()], crate::NulPolicy::Strip).unwrap();
+    assert_eq!(changed, 0);
+    assert_pointer_table(&array, &["x"]);
+
+    let result = CStringArray::new_lossy(Vec::<String>::new(), crate::NulPolicy::Strip);
+    assert!(matches!(result, Err(EmptyArray)));
+}
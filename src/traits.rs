@@ -9,7 +9,12 @@
 //! allow for flexible and convenient array construction from different input
 //! formats.
 
-use std::ffi::CString;
+use alloc::{ffi::CString, string::String, vec::Vec};
+#[cfg(all(unix, feature = "std"))]
+use std::{
+    ffi::{OsStr, OsString},
+    path::PathBuf
+};
 
 use crate::{array::CStringArray, error::CStringArrayError};
 
@@ -147,6 +152,141 @@ impl TryFrom<Vec<CString>> for CStringArray {
     }
 }
 
+impl TryFrom<Vec<Vec<u8>>> for CStringArray {
+    type Error = CStringArrayError;
+
+    /// Converts a vector of byte vectors into a `CStringArray`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any vector contains a null byte or if the outer
+    /// vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let array = CStringArray::try_from(vec![b"a".to_vec(), b"b".to_vec()]).unwrap();
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    fn try_from(strings: Vec<Vec<u8>>) -> Result<Self, Self::Error> {
+        CStringArray::from_bytes_vec(strings)
+    }
+}
+
+impl TryFrom<&[&[u8]]> for CStringArray {
+    type Error = CStringArrayError;
+
+    /// Converts a slice of byte slices into a `CStringArray`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any slice contains a null byte or if the outer
+    /// slice is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let fields: [&[u8]; 2] = [b"key", b"\xfe\xff"];
+    /// let array = CStringArray::try_from(&fields[..]).unwrap();
+    /// assert_eq!(array[1].as_bytes(), b"\xfe\xff");
+    /// ```
+    fn try_from(strings: &[&[u8]]) -> Result<Self, Self::Error> {
+        CStringArray::from_bytes_vec(strings.iter().map(|s| s.to_vec()).collect())
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl TryFrom<Vec<OsString>> for CStringArray {
+    type Error = CStringArrayError;
+
+    /// Converts a `Vec<OsString>` into a `CStringArray` using the raw Unix
+    /// bytes of every string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any string contains an interior null byte or if the
+    /// vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::{convert::TryFrom, ffi::OsString, os::unix::ffi::OsStringExt};
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let strings = vec![OsString::from("ls"), OsString::from_vec(b"\xff".to_vec())];
+    /// let array = CStringArray::try_from(strings).unwrap();
+    /// assert_eq!(array[1].as_bytes(), b"\xff");
+    /// ```
+    fn try_from(strings: Vec<OsString>) -> Result<Self, Self::Error> {
+        CStringArray::from_os_strings(strings)
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl TryFrom<&[&OsStr]> for CStringArray {
+    type Error = CStringArrayError;
+
+    /// Converts a slice of OS string slices into a `CStringArray` using the
+    /// raw Unix bytes of every string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any string contains an interior null byte or if the
+    /// slice is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::{convert::TryFrom, ffi::OsStr};
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let strings = [OsStr::new("hello"), OsStr::new("world")];
+    /// let array = CStringArray::try_from(&strings[..]).unwrap();
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    fn try_from(strings: &[&OsStr]) -> Result<Self, Self::Error> {
+        CStringArray::from_os_strings(strings.iter().copied())
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl TryFrom<Vec<PathBuf>> for CStringArray {
+    type Error = CStringArrayError;
+
+    /// Converts a `Vec<PathBuf>` into a `CStringArray` using the raw Unix
+    /// bytes of every path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any path contains an interior null byte or if the
+    /// vector is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::{convert::TryFrom, path::PathBuf};
+    ///
+    /// use cstring_array::CStringArray;
+    ///
+    /// let paths = vec![PathBuf::from("/etc/hosts"), PathBuf::from("/etc/passwd")];
+    /// let array = CStringArray::try_from(paths).unwrap();
+    /// assert_eq!(array[0].to_str().unwrap(), "/etc/hosts");
+    /// ```
+    fn try_from(paths: Vec<PathBuf>) -> Result<Self, Self::Error> {
+        CStringArray::from_os_strings(paths)
+    }
+}
+
 // ============================================================================
 // Comparison Traits
 // ============================================================================
@@ -159,19 +299,30 @@ impl PartialEq for CStringArray {
 
 impl Eq for CStringArray {}
 
-impl std::hash::Hash for CStringArray {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+impl core::hash::Hash for CStringArray {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.as_slice().hash(state);
     }
 }
 
+// ============================================================================
+// Default Trait
+// ============================================================================
+
+impl Default for CStringArray {
+    /// Creates an empty array, equivalent to [`CStringArray::empty`].
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 // ============================================================================
 // Clone Trait
 // ============================================================================
 
 impl Clone for CStringArray {
     fn clone(&self) -> Self {
-        Self::from_cstrings(self.as_slice().to_vec()).expect("clone from non-empty array")
+        Self::from_cstrings_allow_empty(self.as_slice().to_vec())
     }
 }
 
@@ -180,22 +331,56 @@ impl Clone for CStringArray {
 // ============================================================================
 
 impl FromIterator<String> for CStringArray {
+    /// Collects strings into an array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator is empty or any string contains an interior
+    /// null byte. Use [`CStringArray::try_from_iter`] to handle these cases.
     fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
-        let strings: Vec<String> = iter.into_iter().collect();
-        Self::new(strings).expect("FromIterator from non-empty iterator")
+        Self::try_from_iter(iter)
+            .expect("FromIterator<String> requires at least one string and no interior null bytes")
     }
 }
 
 impl FromIterator<CString> for CStringArray {
+    /// Collects C strings into an array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator is empty. Use [`CStringArray::try_from_iter`]
+    /// to handle this case.
     fn from_iter<I: IntoIterator<Item = CString>>(iter: I) -> Self {
-        let strings: Vec<CString> = iter.into_iter().collect();
-        Self::from_cstrings(strings).expect("FromIterator from non-empty iterator")
+        Self::try_from_iter(iter).expect("FromIterator<CString> requires a non-empty iterator")
+    }
+}
+
+impl Extend<String> for CStringArray {
+    /// Appends every string from the iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any string contains an interior null byte.
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|s| {
+            CString::new(s).expect("Extend<String> requires strings without interior null bytes")
+        }));
+    }
+}
+
+impl Extend<CString> for CStringArray {
+    fn extend<I: IntoIterator<Item = CString>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
     }
 }
 
 impl IntoIterator for CStringArray {
     type Item = CString;
-    type IntoIter = std::vec::IntoIter<CString>;
+    type IntoIter = alloc::vec::IntoIter<CString>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.into_strings().into_iter()
@@ -204,7 +389,7 @@ impl IntoIterator for CStringArray {
 
 impl<'a> IntoIterator for &'a CStringArray {
     type Item = &'a CString;
-    type IntoIter = std::slice::Iter<'a, CString>;
+    type IntoIter = core::slice::Iter<'a, CString>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -215,7 +400,7 @@ impl<'a> IntoIterator for &'a CStringArray {
 // Indexing Traits
 // ============================================================================
 
-impl std::ops::Index<usize> for CStringArray {
+impl core::ops::Index<usize> for CStringArray {
     type Output = CString;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -215,3 +215,12 @@ fn integration_unicode_in_ffi() {
         assert_eq!(CStr::from_ptr(*ptr.offset(2)).to_str().unwrap(), "文件");
     }
 }
+
+#[test]
+fn integration_empty_environment() {
+    let envp = CStringArray::new_allow_empty(Vec::new()).unwrap();
+
+    let count = mock_c_function_verify_null_termination(envp.as_ptr());
+    assert_eq!(count, 0);
+    assert!(envp.is_empty());
+}
@@ -197,3 +197,27 @@ fn slice_consistency() {
         }
     });
 }
+
+// Property 13: Shell Round-trip
+// from_shell_words(to_shell_string()) gives back the same array
+#[test]
+fn shell_round_trip() {
+    proptest!(|(strings in valid_strings())| {
+        let arr = CStringArray::new(strings).unwrap();
+        let line = arr.to_shell_string().unwrap();
+
+        prop_assert_eq!(CStringArray::from_shell_words(&line).unwrap(), arr);
+    });
+}
+
+// Property 14: Shell Byte Round-trip
+// from_shell_bytes(to_shell_bytes()) gives back any array, UTF-8 or not
+#[test]
+fn shell_bytes_round_trip() {
+    proptest!(|(words in prop::collection::vec(prop::collection::vec(1u8..=255, 0..16), 1..16))| {
+        let arr = CStringArray::try_from(words).unwrap();
+        let line = arr.to_shell_bytes();
+
+        prop_assert_eq!(CStringArray::from_shell_bytes(&line).unwrap(), arr);
+    });
+}
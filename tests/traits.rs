@@ -202,3 +202,36 @@ fn test_collect_from_map() {
     assert_eq!(arr[1].to_str().unwrap(), "item2");
     assert_eq!(arr[2].to_str().unwrap(), "item3");
 }
+
+#[test]
+fn test_extend_string() {
+    let mut arr = CStringArray::new(vec!["gcc".to_string()]).unwrap();
+    arr.extend(vec!["-c".to_string(), "main.c".to_string()]);
+
+    assert_eq!(arr.len(), 3);
+    assert_eq!(arr[1].to_str().unwrap(), "-c");
+    assert_eq!(arr[2].to_str().unwrap(), "main.c");
+    unsafe {
+        assert_eq!(*arr.as_ptr().add(2), arr[2].as_ptr());
+        assert!((*arr.as_ptr().add(3)).is_null());
+    }
+}
+
+#[test]
+fn test_extend_cstring() {
+    let mut arr = CStringArray::new(vec!["a".to_string()]).unwrap();
+    arr.extend((0..3).map(|i| CString::new(format!("item{}", i)).unwrap()));
+
+    assert_eq!(arr.len(), 4);
+    assert_eq!(arr[3].to_str().unwrap(), "item2");
+    unsafe {
+        assert!((*arr.as_ptr().add(4)).is_null());
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_extend_string_with_null_byte() {
+    let mut arr = CStringArray::new(vec!["a".to_string()]).unwrap();
+    arr.extend(vec!["b\0c".to_string()]);
+}